@@ -1,102 +1,118 @@
-use std::num::NonZeroU32;
-
 use nerf::*;
 
 use criterion::{
     black_box,
     criterion_group,
     criterion_main,
+    BenchmarkId,
     Criterion,
 };
 
-fn compute_widget_sizes(c: &mut Criterion) {
-    let root = black_box(Column::new(
-        [
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-            Row::new([
-                SizedBox::new(120, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(240, 300, Empty::expand()), Empty::expand(),
-                SizedBox::new(40, 100, Empty::expand()), Empty::expand(),
-                SizedBox::new(10, 80, Empty::expand()), Empty::expand(),
-                SizedBox::new(200, 5, Empty::expand()), Empty::expand(),
-            ]),
-        ]
-    ));
-    let rect = softbuffer::Rect {
-        x: 0,
-        y: 0,
-        width: unsafe { NonZeroU32::new_unchecked(1920) },
-        height: unsafe { NonZeroU32::new_unchecked(1080) },
-    };
-    c.bench_function("Compute size for 100 widgets :", |b| {
+/// One level of the benchmark tree: a padded scaffold with an app bar, and the child aligned in the body.
+fn level<W: Widget<()>>(child: W) -> impl Widget<()> {
+    Padder::new(PaddType::ALL, 2.0, Scaffold::new(
+        ScreenSide::Top,
+        SizedBox::height(4.0, Button::new(Empty::expand())),
+        Align::new(Alignment::CENTER, Padder::new(PaddType::HORIZONTAL, 1.0, child)),
+    ))
+}
+
+/// A grid of cells, each of them being a tree of 8 levels, for a bit more than 600 widgets.
+fn deep_tree() -> Grid<(), impl Widget<()>> {
+    let cell = || level(level(level(level(level(level(level(level(Button::new(Empty::expand())))))))));
+    let mut grid = Grid::new(vec![GridTrack::Flex(1); 4], vec![GridTrack::Flex(1); 4]);
+    for row in 0..4 {
+        for column in 0..4 {
+            grid = grid.with_cell(GridCell::new(row, column, cell()));
+        }
+    }
+    grid
+}
+
+fn cursor_moves() -> Vec<AppEvent<()>> {
+    (0..100).map(|index| AppEvent::CursorMoved { position: MousePosition::InWindow(Point::new(index as f32 * 19., index as f32 * 10.)) }).collect()
+}
+
+fn window_rect() -> Rect {
+    Rect::new(0., 0., 1920., 1080.)
+}
+
+/// Events are dispatched between layout passes, so their cost should not depend on how expensive the layout is.
+/// Without a layout pass, every container computes the rects of its children again for each event.
+fn dispatch_events(c: &mut Criterion) {
+    assert!(responding_events() > 0, "the cursor moves should change the hover state of some buttons");
+    let rect = window_rect();
+    let events = cursor_moves();
+    let mut assets = Assets::new();
+    let mut context = AppContext::new();
+    let mut group = c.benchmark_group("Dispatch 100 cursor moves in 600 widgets");
+    for laid_out in [false, true] {
+        let mut root = deep_tree();
+        if laid_out {
+            root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets));
+        }
+        group.bench_with_input(BenchmarkId::new("laid out", laid_out), &laid_out, |b, _| {
+            b.iter(|| {
+                for event in events.iter() {
+                    black_box(root.handle_event(black_box(event), rect, &mut context));
+                }
+            })
+        });
+    }
+    // the cells changed, as when a widget requests a layout: the stored rects are dropped and the window is laid out again
+    let mut root = deep_tree();
+    group.bench_function("invalidated and laid out again", |b| {
+        b.iter(|| {
+            root.cells_mut();
+            root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets));
+            for event in events.iter() {
+                black_box(root.handle_event(black_box(event), rect, &mut context));
+            }
+        })
+    });
+    // a window dispatches each event with a new context, and lays out again before the next one when asked to.
+    // the cursor crosses buttons that change their hover state, so the root responds to many of the events,
+    // which should not cost a layout pass unless a widget requests one
+    for relayout_on_response in [false, true] {
+        let mut root = deep_tree();
+        root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets));
+        let name = match relayout_on_response {
+            false => "as a window, laid out again on request",
+            true => "as a window, laid out again after each response",
+        };
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut needs_layout = false;
+                for event in events.iter() {
+                    if needs_layout {
+                        root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets));
+                    }
+                    let mut context = AppContext::new();
+                    let responded = !black_box(root.handle_event(black_box(event), rect, &mut context)).is_none();
+                    needs_layout = context.layout_requested() || (relayout_on_response && responded);
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+/// Number of the cursor moves the tree responds to, so the window benchmarks are known to pay for them.
+fn responding_events() -> usize {
+    let rect = window_rect();
+    let mut assets = Assets::new();
+    let mut root = deep_tree();
+    root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets));
+    cursor_moves().iter().filter(|event| !root.handle_event(event, rect, &mut AppContext::new()).is_none()).count()
+}
+
+fn layout_pass(c: &mut Criterion) {
+    let rect = window_rect();
+    let mut assets = Assets::new();
+    let mut root = deep_tree();
+    c.bench_function("Layout pass of 600 widgets", |b| {
         b.iter(|| {
-            // this is SUPER UNSAFE: we are building a fake canvas to pass to the draw function.
-            // however, the widgets won't actually draw anything, so it's fine.
-            let fake_canvas = [0u8; 8];
-            let canvas = unsafe { std::mem::transmute(fake_canvas) };
-            black_box(root.draw(canvas, rect));
+            black_box(root.layout(SizeConstraints::tight_for(rect), &mut LayoutContext::new(&mut assets)));
         })
     });
 }
@@ -105,7 +121,8 @@ fn compute_widget_sizes(c: &mut Criterion) {
 
 criterion_group!(
     benches,
-    compute_widget_sizes,
+    dispatch_events,
+    layout_pass,
 );
 
 criterion_main!(benches);
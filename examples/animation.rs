@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use nerf::*;
+
+
+const IDLE: Color = Color::rgb(200, 220, 255);
+const HOVERED: Color = Color::rgb(150, 180, 240);
+const PRESSED: Color = Color::rgb(90, 120, 200);
+
+/// A switch, which knob slides to the other side when clicked. Its background fades when hovered and pressed.
+pub struct Switch {
+    child: AnimatedBackground<(), Button<(), Empty>>,
+    knob: AnimationController,
+    on: bool,
+}
+
+impl Switch {
+    fn knob_rect(rect: Rect, on: bool) -> Rect {
+        let side = rect.height.min(rect.width);
+        let x = match on {
+            true => rect.right() - side,
+            false => rect.x,
+        };
+        Rect::new(x, rect.y, side, side)
+    }
+}
+
+impl Widget<()> for Switch {
+    type EventResponse = (ButtonResponse, ());
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<()>) {
+        self.child.draw(canvas, rect, context);
+        let knob = self.knob.animate(&Self::knob_rect(rect, false), &Self::knob_rect(rect, true), context);
+        let knob_color = self.knob.animate(&Color::rgb(60, 60, 80), &Color::rgb(255, 200, 60), context);
+        canvas.fill_rect(knob, knob_color);
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<()>, rect: Rect, context: &mut AppContext<()>) -> Self::EventResponse {
+        let response = self.child.handle_event(event, rect, context);
+        if let ButtonResponse::Clicked = response.0 {
+            self.on = !self.on;
+            match self.on {
+                true => self.knob.forward(context),
+                false => self.knob.reverse(context),
+            }
+        }
+        response
+    }
+}
+
+
+fn main() {
+    let background = AnimatedBackground::new(IDLE, Duration::from_millis(150), Button::new(Empty::expand()))
+        .with_response_colors(|(response, _)| match response {
+            ButtonResponse::Changed { new: ButtonState::Idle, .. } => Some(IDLE),
+            ButtonResponse::Changed { new: ButtonState::Hovered, .. } | ButtonResponse::Clicked => Some(HOVERED),
+            ButtonResponse::Changed { new: ButtonState::Pressed | ButtonState::PressedLeft, .. } => Some(PRESSED),
+            ButtonResponse::None => None,
+        });
+    let switch = Switch {
+        child: background,
+        knob: AnimationController::new(Duration::from_millis(300)).with_curve(Curve::EaseOut),
+        on: false,
+    };
+    run_app::<(), _>(Center::new(SizedBox::new(200.0, 80.0, switch)), None).unwrap();
+}
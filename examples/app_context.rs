@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use nerf::*;
+
+
+/// Counts the clicks in the window title, and resets the title a second after the last click.
+pub struct ClickCounter<Child: Widget<()>> {
+    child: Button<(), Child>,
+    clicks: u32,
+    reset_timer: Option<TimerId>,
+}
+
+impl<Child: Widget<()>> Widget<()> for ClickCounter<Child> {
+    type EventResponse = (ButtonResponse, Child::EventResponse);
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<()>) {
+        self.child.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<()>, rect: Rect, context: &mut AppContext<()>) -> Self::EventResponse {
+        if let AppEvent::Timer { id } = event {
+            if Some(*id) == self.reset_timer {
+                self.clicks = 0;
+                context.set_title("Click the button");
+            }
+        }
+        let response = self.child.handle_event(event, rect, context);
+        match response.0 {
+            ButtonResponse::Changed { new: ButtonState::Hovered, prev: ButtonState::Idle } => context.set_cursor_icon(winit::window::CursorIcon::Pointer),
+            ButtonResponse::Changed { new: ButtonState::Idle, .. } => context.set_cursor_icon(winit::window::CursorIcon::Default),
+            ButtonResponse::Clicked => {
+                self.clicks += 1;
+                context.set_title(format!("{} clicks (scale factor {})", self.clicks, context.scale_factor()));
+                if let Some(timer) = self.reset_timer.replace(context.set_timeout(Duration::from_secs(1))) {
+                    context.cancel_timer(timer);
+                }
+            },
+            _ => {},
+        }
+        response
+    }
+}
+
+
+fn main() {
+    let counter = ClickCounter {
+        child: Button::new(SizedBox::new(200.0, 80.0, Background::new(Color::rgb(200, 220, 255), Empty::expand()))),
+        clicks: 0,
+        reset_timer: None,
+    };
+    run_app::<(), _>(Center::new(counter), Some(winit::window::WindowAttributes::default().with_title("Click the button"))).unwrap();
+}
@@ -0,0 +1,14 @@
+use nerf::*;
+
+
+
+fn main() {
+    // the video preview keeps a 16:9 ratio whatever the window size is,
+    // and the side panel takes a part of the side bar.
+    let preview = Center::new(AspectRatio::new(16.0 / 9.0, Background::new(Color::rgb(20, 20, 20), Empty::expand())));
+    let side_panel = SizedBox::width(300.0, FractionallySizedBox::new(0.8, 0.5, Background::new(Color::rgb(70, 90, 140), Empty::expand())));
+    run_app::<(), _>(Background::new(
+        Color::rgb(50, 50, 60),
+        Padder::new(PaddType::ALL, 20.0, Scaffold::new(ScreenSide::Right, side_panel, preview)),
+    ), None).unwrap();
+}
@@ -0,0 +1,85 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use nerf::*;
+
+
+/// Result of a computation running on another thread, that can be awaited.
+struct ThreadResult<T> {
+    shared: Arc<Mutex<(Option<T>, Option<Waker>)>>,
+}
+
+impl<T: Send + 'static> ThreadResult<T> {
+    fn spawn(work: impl FnOnce() -> T + Send + 'static) -> Self {
+        let shared = Arc::new(Mutex::new((None, None::<Waker>)));
+        let thread_shared = shared.clone();
+        std::thread::spawn(move || {
+            let result = work();
+            let mut shared = thread_shared.lock().unwrap();
+            shared.0 = Some(result);
+            if let Some(waker) = shared.1.take() {
+                waker.wake();
+            }
+        });
+        ThreadResult { shared }
+    }
+}
+
+impl<T> Future for ThreadResult<T> {
+    type Output = T;
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.0.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.1 = Some(context.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// Counts primes in the background when clicked, and shows the count in the window title.
+pub struct PrimeCounter<Child: Widget<usize>> {
+    child: Button<usize, Child>,
+    counting: bool,
+}
+
+impl<Child: Widget<usize>> Widget<usize> for PrimeCounter<Child> {
+    type EventResponse = (ButtonResponse, Child::EventResponse);
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<usize>) {
+        self.child.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<usize>, rect: Rect, context: &mut AppContext<usize>) -> Self::EventResponse {
+        if let AppEvent::Custom(count) = event {
+            self.counting = false;
+            context.set_title(format!("{count} primes below 5 000 000"));
+        }
+        let response = self.child.handle_event(event, rect, context);
+        if response.0 == ButtonResponse::Clicked && !self.counting {
+            self.counting = true;
+            context.set_title("Counting...");
+            // the ui keeps responding while the primes are counted
+            context.spawn(async {
+                ThreadResult::spawn(|| (2..5_000_000u64).filter(|n| (2..).take_while(|d| d * d <= *n).all(|d| n % d != 0)).count()).await
+            });
+        }
+        response
+    }
+}
+
+
+fn main() {
+    let counter = PrimeCounter {
+        child: Button::new(SizedBox::new(200.0, 80.0, Background::new(Color::rgb(200, 220, 255), Empty::expand()))),
+        counting: false,
+    };
+    run_app::<usize, _>(Center::new(counter), Some(winit::window::WindowAttributes::default().with_title("Click to count primes"))).unwrap();
+}
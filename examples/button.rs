@@ -13,14 +13,14 @@ impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for EventPrinter<Use
 where Child::EventResponse: Debug
 {
     type EventResponse = Child::EventResponse;
-    fn draw(&self, canvas: &mut Canvas, rect: Rect) {
-        self.child.draw(canvas, rect)
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, rect, context)
     }
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
         self.child.min_space_requirements()
     }
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: nerf::Rect) -> Self::EventResponse {
-        let response = self.child.handle_event(event, rect);
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: nerf::Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let response = self.child.handle_event(event, rect, context);
         if !response.is_none() {   
             println!("Received response: {response:?}");
         }
@@ -36,7 +36,7 @@ fn main() {
             _m: core::marker::PhantomData,
             child: Button::new(
                 SizedBox::new(
-                    200, 80,
+                    200.0, 80.0,
                     Background::new(
                         Color::rgb(200, 220, 255),
                         Empty::expand()
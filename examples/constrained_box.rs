@@ -0,0 +1,15 @@
+use nerf::*;
+
+
+
+fn main() {
+    // the card grows with the window, but never gets narrower than 200 pixels nor wider than 600.
+    let card = ConstrainedBox::new(Background::new(Color::rgb(70, 90, 140), Empty::expand()))
+        .min_width(200.0)
+        .max_width(600.0)
+        .max_height(400.0);
+    run_app::<(), _>(Background::new(
+        Color::rgb(50, 50, 60),
+        Padder::new(PaddType::ALL, 20.0, Center::new(card)),
+    ), None).unwrap();
+}
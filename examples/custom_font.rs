@@ -0,0 +1,31 @@
+use nerf::*;
+
+
+
+fn main() {
+    // fonts can also be bundled with the app, using assets.text_mut().load_font_bytes(include_bytes!("path/to/font.ttf"))
+    let mut assets = Assets::new();
+    let family = match std::env::args().nth(1) {
+        Some(path) => match assets.text_mut().load_font_file(&path) {
+            Ok(families) => families.first().map(|name| FontFamily::Name(name.clone())),
+            Err(error) => {
+                println!("Failed to load font {path}: {error}");
+                None
+            }
+        },
+        None => None,
+    };
+    // if the font is not available, the text will be drawn with the first available fallback
+    assets.text_mut().set_family_fallbacks(vec![FontFamily::Monospace]);
+
+    run_app_with_assets(Align::new(
+        Alignment::CENTER,
+        Text::<()>::new(
+            "The quick brown fox jumps over the lazy dog".to_string(),
+            TextStyle::default()
+                .sized(24.0)
+                .with_font(family.unwrap_or(FontFamily::Name("Missing Brand Font".to_string())))
+                .colored(Color::WHITE),
+        ),
+    ), None, assets).unwrap();
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use nerf::*;
+
+
+/// Changes color each time a beat is received from the background thread.
+pub struct Heartbeat {
+    background: Background<u32, Empty>,
+}
+
+impl Widget<u32> for Heartbeat {
+    type EventResponse = ();
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<u32>) {
+        self.background.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        Widget::<u32>::min_space_requirements(&self.background)
+    }
+    fn handle_event(&mut self, event: &AppEvent<u32>, rect: Rect, context: &mut AppContext<u32>) -> Self::EventResponse {
+        if let AppEvent::Custom(beat) = event {
+            let shade = if beat % 2 == 0 { 200 } else { 120 };
+            self.background.set_color(Color::rgb(shade, 60, 80));
+            context.request_redraw();
+        }
+        self.background.handle_event(event, rect, context);
+    }
+}
+
+
+fn main() {
+    let app = App::<u32>::new();
+    let sender = app.event_sender();
+    std::thread::spawn(move || {
+        // stop once the app exited and gives the events back
+        for beat in 0.. {
+            std::thread::sleep(Duration::from_millis(500));
+            if sender.send(beat).is_err() {
+                break;
+            }
+        }
+    });
+    app.with_window(
+        winit::window::WindowAttributes::default().with_title("Heartbeat"),
+        Heartbeat { background: Background::new(Color::rgb(120, 60, 80), Empty::expand()) },
+    ).run().unwrap();
+}
@@ -0,0 +1,32 @@
+use nerf::*;
+
+
+
+type Cell = Box<dyn Widget<(), EventResponse = ()>>;
+
+fn label(text: &str) -> Cell {
+    Box::new(Text::new(
+        text.to_string(),
+        TextStyle::default().sized(18.0).colored(Color::WHITE).vertically_aligned(VerticalAlignment::Center),
+    ))
+}
+
+fn panel(color: Color) -> Cell {
+    Box::new(Background::new(color, Empty::expand()))
+}
+
+fn main() {
+    let grid = Grid::new(
+        vec![GridTrack::Fixed(160.0), GridTrack::Flex(1), GridTrack::Flex(2)],
+        vec![GridTrack::Fixed(50.0), GridTrack::MinMax { min: 100.0, max: 300.0, flex: 1 }, GridTrack::Flex(1)],
+    )
+        .with_gaps(10.0, 10.0)
+        .with_cell(GridCell::new(0, 0, label("Dashboard")))
+        .with_cell(GridCell::new(0, 1, panel(Color::rgb(60, 60, 80))).spanning(1, 2))
+        .with_cell(GridCell::new(1, 0, panel(Color::rgb(80, 50, 50))).spanning(2, 1))
+        .with_cell(GridCell::new(1, 1, panel(Color::rgb(50, 80, 50))).with_margin(EdgeInsets::all(10.0)))
+        .with_cell(GridCell::new(1, 2, Box::new(SizedBox::new(80.0, 40.0, Background::new(Color::rgb(200, 160, 60), Empty::expand()))) as Cell).aligned(Alignment::CENTER))
+        .with_cell(GridCell::new(2, 1, panel(Color::rgb(50, 50, 90))).spanning(1, 2));
+
+    run_app(Padder::new(PaddType::ALL, 20.0, grid), None).unwrap();
+}
@@ -0,0 +1,48 @@
+use nerf::*;
+
+
+/// Shows the scale factor of the window in its title, and updates it when the window moves to another monitor.
+pub struct ScaleFactorTitle<Child: Widget<()>> {
+    child: Child,
+    shown: Option<f64>,
+}
+
+impl<Child: Widget<()>> Widget<()> for ScaleFactorTitle<Child> {
+    type EventResponse = Child::EventResponse;
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<()>) {
+        self.child.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        self.child.layout(constraints, context)
+    }
+    fn handle_event(&mut self, event: &AppEvent<()>, rect: Rect, context: &mut AppContext<()>) -> Self::EventResponse {
+        let scale_factor = match event {
+            AppEvent::ScaleFactorChanged { scale_factor } => *scale_factor,
+            _ => context.scale_factor(),
+        };
+        if self.shown != Some(scale_factor) {
+            self.shown = Some(scale_factor);
+            context.set_title(format!("Scale factor {scale_factor}"));
+        }
+        self.child.handle_event(event, rect, context)
+    }
+}
+
+
+fn main() {
+    // sizes, borders and text are given in logical pixels, so the box looks the same on any monitor
+    let content = SizedBox::new(
+        240.0, 120.0,
+        Background::new(
+            Color::rgb(200, 220, 255),
+            Center::new(Text::new(
+                "240 x 120 logical pixels".to_string(),
+                TextStyle::default().sized(16.0).colored(Color::BLACK),
+            )),
+        ),
+    );
+    run_app::<(), _>(Center::new(ScaleFactorTitle { child: content, shown: None }), None).unwrap();
+}
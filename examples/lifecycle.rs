@@ -0,0 +1,46 @@
+use nerf::*;
+
+
+/// Pretends the document has unsaved changes: the first close request is refused.
+struct UnsavedChanges {
+    warned: bool,
+}
+
+impl AppDelegate<()> for UnsavedChanges {
+    fn started(&mut self, _windows: &Windows<()>) {
+        println!("App started");
+    }
+
+    fn close_requested(&mut self, _window: WindowId, _windows: &Windows<()>) -> bool {
+        if self.warned {
+            return true;
+        }
+        println!("There are unsaved changes, close the window again to discard them.");
+        self.warned = true;
+        false
+    }
+
+    fn suspended(&mut self, _windows: &Windows<()>) {
+        println!("App suspended");
+    }
+
+    fn resumed(&mut self, _windows: &Windows<()>) {
+        println!("App resumed");
+    }
+
+    fn exiting(&mut self) {
+        println!("App exiting");
+    }
+}
+
+
+fn main() {
+    App::<()>::new()
+        .with_delegate(UnsavedChanges { warned: false })
+        .with_window(
+            winit::window::WindowAttributes::default().with_title("Document"),
+            Background::new(Color::rgb(240, 240, 230), Empty::expand()),
+        )
+        .run()
+        .unwrap();
+}
@@ -0,0 +1,22 @@
+use nerf::*;
+
+
+
+fn main() {
+    run_app::<(), _>(ListView::new(
+        100_000,
+        |index, _recycled| SizedBox::height(
+            32.0,
+            Background::new(
+                if index % 2 == 0 { Color::rgb(40, 40, 50) } else { Color::rgb(50, 50, 62) },
+                Text::new(
+                    format!("Row number {index}"),
+                    TextStyle::default()
+                        .sized(16.0)
+                        .colored(Color::WHITE)
+                        .vertically_aligned(VerticalAlignment::Center),
+                ),
+            ),
+        ),
+    ), None).unwrap();
+}
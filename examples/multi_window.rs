@@ -0,0 +1,47 @@
+use nerf::*;
+
+
+/// Opens a new palette window each time the button is clicked.
+pub struct PaletteOpener<Child: Widget<()>> {
+    windows: Windows<()>,
+    child: Button<(), Child>,
+    opened: u32,
+}
+
+impl<Child: Widget<()>> Widget<()> for PaletteOpener<Child> {
+    type EventResponse = (ButtonResponse, Child::EventResponse);
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<()>) {
+        self.child.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<()>, rect: Rect, context: &mut AppContext<()>) -> Self::EventResponse {
+        let response = self.child.handle_event(event, rect, context);
+        if response.0 == ButtonResponse::Clicked {
+            self.opened += 1;
+            let color = Color::rgb((60 * self.opened % 255) as u8, 120, 160);
+            self.windows.open(
+                winit::window::WindowAttributes::default()
+                    .with_title(format!("Palette {}", self.opened))
+                    .with_inner_size(winit::dpi::LogicalSize::new(200, 300)),
+                Background::new(color, Empty::expand()),
+            );
+        }
+        response
+    }
+}
+
+
+fn main() {
+    let app = App::<()>::new();
+    let opener = PaletteOpener {
+        windows: app.windows(),
+        child: Button::new(SizedBox::new(200.0, 80.0, Background::new(Color::rgb(200, 220, 255), Empty::expand()))),
+        opened: 0,
+    };
+    // the app runs until the main window and all the palettes are closed
+    app.with_window(winit::window::WindowAttributes::default().with_title("Main"), Center::new(opener))
+        .run()
+        .unwrap();
+}
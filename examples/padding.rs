@@ -3,13 +3,15 @@ use nerf::*;
 
 
 fn main() {
-    run_app::<(), _>(Padder::new(
-        PaddType::ALL,
-        20,
+    // each side can have its own padding
+    run_app::<(), _>(Padder::insets(
+        EdgeInsets::new(20.0, 40.0, 80.0, 10.0),
         Background::new(
             Color::rgb(220, 255, 230),
-            Empty::expand(),
+            Padder::insets(
+                EdgeInsets::symmetric(10.0, 30.0),
+                Background::new(Color::rgb(120, 180, 140), Empty::expand()),
+            ),
         ),
     ), None).unwrap();
 }
-
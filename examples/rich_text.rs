@@ -0,0 +1,27 @@
+use nerf::*;
+
+
+
+fn main() {
+    run_app(Align::new(
+        Alignment::CENTER,
+        RichText::<()>::new(
+            vec![
+                TextSpan::from("fn "),
+                TextSpan::from("main").colored(Color::rgb(220, 220, 120)).weighted(FontWeight::BOLD),
+                TextSpan::from("() { "),
+                TextSpan::from("// nerf").colored(Color::rgb(120, 160, 120)).italic(),
+                TextSpan::from(" }\n"),
+                TextSpan::from("Rich").sized(30.0).underlined(),
+                TextSpan::from(" text").struck_through(),
+                TextSpan::from(" rocks!").highlighted(Color::rgb(80, 80, 200)),
+                TextSpan::from("\nSpell "),
+                TextSpan::from("chekc").decorated(TextDecoration::spell_check(Color::rgb(230, 60, 60))),
+                TextSpan::from(" and "),
+                TextSpan::from("dotted").decorated(TextDecoration::overline().styled(DecorationStyle::Dotted).thick(2.0)),
+            ],
+            TextStyle::default()
+                .colored(Color::WHITE),
+        ),
+    ), None).unwrap();
+}
@@ -6,7 +6,7 @@ fn main() {
     run_app::<(), _>(Scaffold::new(
         ScreenSide::Top,
         SizedBox::height(
-            100,
+            100.0,
             Background::new(
                 Color::rgb(0, 0, 255),
                 Empty::expand(),
@@ -15,7 +15,7 @@ fn main() {
         Scaffold::new(
             ScreenSide::Left,
             SizedBox::width(
-                100,
+                100.0,
                 Background::new(
                     Color::rgb(40, 40, 0),
                     Empty::shrink())),
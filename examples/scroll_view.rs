@@ -0,0 +1,31 @@
+use nerf::*;
+
+
+
+fn main() {
+    run_app::<(), _>(Padder::new(
+        PaddType::ALL,
+        40.0,
+        ScrollView::new(
+            ScrollAxis::Both,
+            SizedBox::new(
+                1200.0,
+                2000.0,
+                Background::new(
+                    Color::rgb(40, 40, 60),
+                    Padder::new(
+                        PaddType::ALL,
+                        100.0,
+                        Background::new(
+                            Color::rgb(200, 120, 60),
+                            Text::new(
+                                "Scroll with the wheel or the touchpad, or drag the scrollbars.".to_string(),
+                                TextStyle::default().sized(24.0).colored(Color::WHITE),
+                            ),
+                        ),
+                    ),
+                ),
+            ),
+        ),
+    ), None).unwrap();
+}
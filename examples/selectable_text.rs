@@ -0,0 +1,44 @@
+use nerf::*;
+
+
+
+/// Prints the selected text, as a real app would copy it to the clipboard.
+struct SelectionPrinter<UserEvent> {
+    text: SelectableText<UserEvent>,
+}
+
+impl<UserEvent> Widget<UserEvent> for SelectionPrinter<UserEvent> {
+    type EventResponse = SelectionResponse;
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.text.draw(canvas, rect, context)
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.text.min_space_requirements()
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let response = self.text.handle_event(event, rect, context);
+        match &response {
+            SelectionResponse::Selected(text) => println!("Selected: {text:?}"),
+            SelectionResponse::Cleared => println!("Selection cleared"),
+            SelectionResponse::None => {},
+        }
+        response
+    }
+}
+
+fn main() {
+    run_app(Padder::new(
+        PaddType::ALL,
+        50.0,
+        SelectionPrinter::<()> {
+            text: SelectableText::new(
+                "Drag over this text to select it.\nDouble click selects a single word.".to_string(),
+                TextStyle::default()
+                    .sized(20.0)
+                    .colored(Color::WHITE),
+            ),
+        },
+    ), None).unwrap();
+}
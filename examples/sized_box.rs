@@ -6,12 +6,12 @@ fn main() {
     run_app::<(), _>(Align::new(
         Alignment::CENTER,
         SizedBox::new(
-            400,
-            300,
+            400.0,
+            300.0,
             Background::new(
                 Color::rgb(255, 0, 0),
                 SizedBox::height(
-                    50,
+                    50.0,
                     Background::new(
                         Color::rgb(0, 255, 0),
                         Empty::shrink(),
@@ -0,0 +1,46 @@
+use std::fmt::Debug;
+
+use nerf::*;
+
+
+
+struct ResponsePrinter<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for ResponsePrinter<UserEvent, Child>
+where Child::EventResponse: Debug
+{
+    type EventResponse = Child::EventResponse;
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let response = self.child.handle_event(event, rect, context);
+        if !response.is_none() {
+            println!("Received response: {response:?}");
+        }
+        response
+    }
+}
+
+fn main() {
+    // the button in the corner captures clicks, so the big button below does not receive them
+    run_app::<(), _>(ResponsePrinter {
+        _m: core::marker::PhantomData,
+        child: Stack::new(
+            Button::new(Background::new(Color::rgb(40, 60, 90), Empty::expand())),
+            Positioned::new(
+                Button::new(Background::new(Color::rgb(220, 120, 60), Empty::expand())),
+            ).right(20.0).bottom(20.0).width(120.0).height(60.0),
+        ).stop_propagation().on_top(
+            Positioned::new(
+                Text::new("Overlay".to_string(), TextStyle::default().sized(20.0).colored(Color::WHITE)),
+            ).left(20.0).top(20.0).width(200.0).height(40.0),
+        ),
+    }, None).unwrap();
+}
@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use nerf::*;
+
+
+/// A square that blinks while it is clicked on, and shows in the window title how long the window has been open.
+pub struct Blinker {
+    button: Button<(), Empty>,
+    lit: bool,
+    blink_timer: Option<TimerId>,
+    clock_timer: Option<TimerId>,
+    seconds: u32,
+}
+
+impl Widget<()> for Blinker {
+    type EventResponse = ();
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, _context: &mut AppContext<()>) {
+        let color = match self.lit {
+            true => Color::rgb(255, 200, 60),
+            false => Color::rgb(60, 60, 80),
+        };
+        canvas.fill_rect(rect, color);
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.button.min_space_requirements()
+    }
+    fn handle_event(&mut self, event: &AppEvent<()>, rect: Rect, context: &mut AppContext<()>) {
+        // the clock starts with the first event the window receives
+        if self.clock_timer.is_none() {
+            self.clock_timer = Some(context.set_interval(Duration::from_secs(1)));
+        }
+        if let AppEvent::Timer { id } = event {
+            if Some(*id) == self.blink_timer {
+                self.lit = !self.lit;
+                context.request_redraw();
+            }
+            else if Some(*id) == self.clock_timer {
+                self.seconds += 1;
+                context.set_title(format!("Open for {}s", self.seconds));
+            }
+        }
+        if let (ButtonResponse::Clicked, ()) = self.button.handle_event(event, rect, context) {
+            match self.blink_timer.take() {
+                Some(timer) => {
+                    context.cancel_timer(timer);
+                    self.lit = false;
+                    context.request_redraw();
+                },
+                None => self.blink_timer = Some(context.set_interval(Duration::from_millis(400))),
+            }
+        }
+    }
+}
+
+
+fn main() {
+    let blinker = Blinker {
+        button: Button::new(Empty::expand()),
+        lit: false,
+        blink_timer: None,
+        clock_timer: None,
+        seconds: 0,
+    };
+    run_app::<(), _>(Center::new(SizedBox::new(120.0, 120.0, blinker)), Some(winit::window::WindowAttributes::default().with_title("Click the square"))).unwrap();
+}
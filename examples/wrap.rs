@@ -0,0 +1,17 @@
+use nerf::*;
+
+
+
+fn chip(width: f32, color: Color) -> SizedBox<(), Background<(), Empty>> {
+    SizedBox::new(width, 32.0, Background::new(color, Empty::expand()))
+}
+
+fn main() {
+    let colors = [Color::rgb(200, 90, 90), Color::rgb(90, 170, 90), Color::rgb(90, 120, 210), Color::rgb(210, 170, 60)];
+    let chips = (0..30).map(|index| chip((40 + index * 37 % 90) as f32, colors[index as usize % colors.len()])).collect();
+    let wrap = Wrap::new(chips)
+        .spaced(8.0, 12.0)
+        .with_run_alignment(HorizontalAlignment::Center);
+
+    run_app(Padder::new(PaddType::ALL, 20.0, wrap), None).unwrap();
+}
@@ -0,0 +1,19 @@
+use nerf::*;
+
+
+
+fn main() {
+    // the scroll view lays out the text with the view width and an unbounded height,
+    // so the text measures how tall its wrapped lines are, and the content height follows the window width.
+    let paragraph = "The layout pass gives each widget constraints, and the widget answers with its size. ".repeat(60);
+    run_app::<(), _>(Padder::new(
+        PaddType::ALL,
+        40.0,
+        ScrollView::vertical(
+            Background::new(
+                Color::rgb(40, 40, 60),
+                Text::new(paragraph, TextStyle::default().sized(20.0).colored(Color::WHITE)),
+            ),
+        ),
+    ), None).unwrap();
+}
@@ -0,0 +1,4 @@
+pub(crate) mod animated;
+pub(crate) mod controller;
+pub(crate) mod curve;
+pub(crate) mod interpolate;
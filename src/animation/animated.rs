@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use crate::app::app_context::AppContext;
+
+use super::{controller::AnimationController, curve::Curve, interpolate::Interpolate};
+
+/// A value that animates implicitly: setting a new target runs an animation from the current value to it.
+#[derive(Debug, Clone)]
+pub struct Animation<T: Interpolate + Clone> {
+    from: T,
+    to: T,
+    controller: AnimationController,
+}
+
+impl<T: Interpolate + Clone> Animation<T> {
+    /// Create a settled animation, that takes the duration to go to each new target.
+    pub fn new(value: T, duration: Duration) -> Self {
+        let mut controller = AnimationController::new(duration);
+        controller.set_progress(1.0);
+        Animation { from: value.clone(), to: value, controller }
+    }
+
+    pub fn with_curve(self, curve: Curve) -> Self {
+        Animation { controller: self.controller.with_curve(curve), ..self }
+    }
+
+    /// The value the animation goes to, or is at once settled.
+    pub fn target(&self) -> &T {
+        &self.to
+    }
+
+    /// Animate from the current value to the target.
+    pub fn animate_to<UserEvent>(&mut self, target: T, context: &mut AppContext<UserEvent>) {
+        self.from = self.from.interpolate(&self.to, self.controller.value_at(context.frame_time()));
+        self.to = target;
+        self.controller.set_progress(0.0);
+        self.controller.forward(context);
+    }
+
+    /// Jump to the value, without animating.
+    pub fn set(&mut self, value: T) {
+        self.from = value.clone();
+        self.to = value;
+        self.controller.set_progress(1.0);
+    }
+
+    /// Value for the current frame. While the animation is running, this requests the next frame.
+    pub fn value<UserEvent>(&self, context: &mut AppContext<UserEvent>) -> T {
+        self.controller.animate(&self.from, &self.to, context)
+    }
+}
@@ -0,0 +1,175 @@
+use std::time::{Duration, Instant};
+
+use crate::app::app_context::AppContext;
+
+use super::{curve::Curve, interpolate::Interpolate};
+
+/// Drives an animation, from 0 when dismissed to 1 when completed.
+///
+/// The controller is driven by the frame time of the context: widgets read its value when they draw,
+/// and the controller requests the next frame as long as it is running. A settled controller costs no redraw.
+#[derive(Debug, Clone, Copy)]
+pub struct AnimationController {
+    duration: Duration,
+    curve: Curve,
+    /// Linear progress when the current run started.
+    from: f32,
+    /// Linear progress the current run goes to.
+    to: f32,
+    /// Frame time the current run started at, None if it never ran.
+    start: Option<Instant>,
+}
+
+impl AnimationController {
+    /// Create a dismissed controller, that takes the duration to go from 0 to 1.
+    pub fn new(duration: Duration) -> Self {
+        AnimationController {
+            duration,
+            curve: Curve::default(),
+            from: 0.0,
+            to: 0.0,
+            start: None,
+        }
+    }
+
+    pub fn with_curve(self, curve: Curve) -> Self {
+        AnimationController { curve, ..self }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn curve(&self) -> Curve {
+        self.curve
+    }
+
+    /// Run the animation towards 1, from its current value.
+    pub fn forward<UserEvent>(&mut self, context: &mut AppContext<UserEvent>) {
+        self.animate_to(1.0, context);
+    }
+
+    /// Run the animation towards 0, from its current value.
+    pub fn reverse<UserEvent>(&mut self, context: &mut AppContext<UserEvent>) {
+        self.animate_to(0.0, context);
+    }
+
+    /// Run the animation towards the given linear progress, clamped to [0, 1].
+    /// Going over part of the range takes the same part of the duration.
+    pub fn animate_to<UserEvent>(&mut self, progress: f32, context: &mut AppContext<UserEvent>) {
+        let now = context.frame_time();
+        self.from = self.progress_at(now);
+        self.to = progress.clamp(0.0, 1.0);
+        self.start = Some(now);
+        context.request_redraw();
+    }
+
+    /// Stop the animation where it is.
+    pub fn stop<UserEvent>(&mut self, context: &AppContext<UserEvent>) {
+        self.set_progress(self.progress_at(context.frame_time()));
+    }
+
+    /// Jump to the given linear progress, clamped to [0, 1], without animating.
+    pub fn set_progress(&mut self, progress: f32) {
+        self.from = progress.clamp(0.0, 1.0);
+        self.to = self.from;
+        self.start = None;
+    }
+
+    /// Linear progress of the animation at the given time, before the curve is applied.
+    pub fn progress_at(&self, time: Instant) -> f32 {
+        let start = match self.start {
+            Some(start) => start,
+            None => return self.to,
+        };
+        let run_duration = self.duration.mul_f32((self.to - self.from).abs());
+        let elapsed = time.saturating_duration_since(start);
+        match elapsed >= run_duration {
+            true => self.to,
+            false => self.from.interpolate(&self.to, elapsed.as_secs_f32() / run_duration.as_secs_f32()),
+        }
+    }
+
+    /// Whether the animation is still running at the given time.
+    pub fn is_running_at(&self, time: Instant) -> bool {
+        self.progress_at(time) != self.to
+    }
+
+    /// Value of the animation at the given time, with the curve applied.
+    pub fn value_at(&self, time: Instant) -> f32 {
+        self.curve.transform(self.progress_at(time))
+    }
+
+    /// Value of the animation for the current frame.
+    /// While the animation is running, this requests the next frame, so it should be called when drawing.
+    pub fn value<UserEvent>(&self, context: &mut AppContext<UserEvent>) -> f32 {
+        let now = context.frame_time();
+        if self.is_running_at(now) {
+            context.request_redraw();
+        }
+        self.value_at(now)
+    }
+
+    /// Value between begin and end for the current frame, as given by `value`.
+    pub fn animate<T: Interpolate, UserEvent>(&self, begin: &T, end: &T, context: &mut AppContext<UserEvent>) -> T {
+        begin.interpolate(end, self.value(context))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::AnimationController;
+    use crate::{animation::curve::Curve, AppContext};
+
+    fn context_at(time: Instant) -> AppContext<()> {
+        AppContext::new().with_frame_time(time)
+    }
+
+    fn assert_close(value: f32, expected: f32) {
+        assert!((value - expected).abs() < 1e-4, "{value} is not {expected}");
+    }
+
+    #[test]
+    fn forward_runs_for_the_duration() {
+        let start = Instant::now();
+        let mut controller = AnimationController::new(Duration::from_millis(100)).with_curve(Curve::Linear);
+        controller.forward(&mut context_at(start));
+        assert_close(controller.value_at(start), 0.0);
+        assert_close(controller.value_at(start + Duration::from_millis(50)), 0.5);
+        assert_close(controller.value_at(start + Duration::from_millis(100)), 1.0);
+        assert_close(controller.value_at(start + Duration::from_secs(1)), 1.0);
+    }
+
+    #[test]
+    fn reverse_starts_from_the_current_value() {
+        let start = Instant::now();
+        let mut controller = AnimationController::new(Duration::from_millis(100)).with_curve(Curve::Linear);
+        controller.forward(&mut context_at(start));
+        let half = start + Duration::from_millis(50);
+        controller.reverse(&mut context_at(half));
+        assert_close(controller.value_at(half), 0.5);
+        // going back over half of the range takes half of the duration
+        assert_close(controller.value_at(half + Duration::from_millis(25)), 0.25);
+        assert_close(controller.value_at(half + Duration::from_millis(50)), 0.0);
+    }
+
+    #[test]
+    fn redraws_are_requested_only_while_running() {
+        let start = Instant::now();
+        let mut controller = AnimationController::new(Duration::from_millis(100));
+        let mut settled = context_at(start);
+        controller.value(&mut settled);
+        assert!(!settled.redraw_requested());
+
+        controller.forward(&mut context_at(start));
+        let mut running = context_at(start + Duration::from_millis(50));
+        controller.value(&mut running);
+        assert!(running.redraw_requested());
+
+        let mut done = context_at(start + Duration::from_millis(100));
+        assert_eq!(controller.value(&mut done), 1.0);
+        assert!(!done.redraw_requested());
+    }
+}
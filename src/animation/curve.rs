@@ -0,0 +1,33 @@
+/// Easing curve of an animation, mapping the linear progress of the animation to the progress of its value.
+///
+/// All curves start at 0 and end at 1.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Curve {
+    Linear,
+    /// Starts slowly and speeds up.
+    EaseIn,
+    /// Starts fast and slows down.
+    EaseOut,
+    /// Starts slowly, speeds up and slows down at the end.
+    #[default]
+    EaseInOut,
+    /// Any function from [0, 1] to [0, 1], with f(0) = 0 and f(1) = 1.
+    Custom(fn(f32) -> f32),
+}
+
+impl Curve {
+    /// Value of the curve for the progress t, that is clamped to [0, 1].
+    pub fn transform(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Curve::Linear => t,
+            Curve::EaseIn => t * t * t,
+            Curve::EaseOut => 1.0 - (1.0 - t) * (1.0 - t) * (1.0 - t),
+            Curve::EaseInOut => match t < 0.5 {
+                true => 4.0 * t * t * t,
+                false => 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0,
+            },
+            Curve::Custom(function) => function(t),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{drawing::color::Color, Offset, Point, Rect, Size};
+
+/// Values that can be animated, by computing the values between two of them.
+pub trait Interpolate {
+    /// The value at t between self (t = 0) and other (t = 1).
+    fn interpolate(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t as f64
+    }
+}
+
+impl Interpolate for u8 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (*self as f32).interpolate(&(*other as f32), t).round().clamp(0.0, u8::MAX as f32) as u8
+    }
+}
+
+impl Interpolate for u32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (*self as f64).interpolate(&(*other as f64), t).round().clamp(0.0, u32::MAX as f64) as u32
+    }
+}
+
+impl Interpolate for i32 {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (*self as f64).interpolate(&(*other as f64), t).round() as i32
+    }
+}
+
+/// Pairs of values, such as (width, height).
+impl<T: Interpolate> Interpolate for (T, T) {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        (self.0.interpolate(&other.0, t), self.1.interpolate(&other.1, t))
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Color::rgba(
+            self.r.interpolate(&other.r, t),
+            self.g.interpolate(&other.g, t),
+            self.b.interpolate(&other.b, t),
+            self.a.interpolate(&other.a, t),
+        )
+    }
+}
+
+impl Interpolate for Rect {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Rect {
+            x: self.x.interpolate(&other.x, t),
+            y: self.y.interpolate(&other.y, t),
+            width: self.width.interpolate(&other.width, t),
+            height: self.height.interpolate(&other.height, t),
+        }
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Point::new(self.x.interpolate(&other.x, t), self.y.interpolate(&other.y, t))
+    }
+}
+
+impl Interpolate for Size {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Size::new(self.width.interpolate(&other.width, t), self.height.interpolate(&other.height, t))
+    }
+}
+
+impl Interpolate for Offset {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Offset::new(self.dx.interpolate(&other.dx, t), self.dy.interpolate(&other.dy, t))
+    }
+}
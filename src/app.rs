@@ -1,140 +1,424 @@
-use std::num::NonZeroU32;
-
+use app_context::AppContext;
+use delegate::{AppDelegate, NoDelegate};
+use error::NerfError;
+use event_sender::{EventSender, RuntimeEvent, TaskWakeUp};
+use executor::{Executor, LocalExecutor};
+use timers::Timers;
 use event::AppEvent;
+use window::{AppWindow, WindowId};
+use windows::{WindowRequest, Windows};
 use winit::platform::wayland::WindowAttributesExtWayland;
 
-use crate::{widget::Widget, Canvas};
+use crate::widget::Widget;
 
 pub(crate) mod app_context;
 pub(crate) mod assets;
+pub(crate) mod delegate;
+pub(crate) mod error;
 pub(crate) mod event;
+pub(crate) mod event_sender;
+pub(crate) mod executor;
+pub(crate) mod timers;
+pub(crate) mod window;
+pub(crate) mod windows;
 
-enum GraphicState {
-    Created {
-        window: std::rc::Rc<winit::window::Window>,
-        _context: softbuffer::Context<std::rc::Rc<winit::window::Window>>,
-        surface: softbuffer::Surface<std::rc::Rc<winit::window::Window>, std::rc::Rc<winit::window::Window>>,
-    },
-    ToBeCreated {
-        window_attributes: winit::window::WindowAttributes
-    },
-    Failed {
-        error: winit::error::OsError,
-    }
-}
-
-pub struct ApplicationData<UserEvent, Root: Widget<UserEvent>> {
-    _m: core::marker::PhantomData<UserEvent>,
-    graphic_state: GraphicState,
-    root: Root,
+pub(crate) struct ApplicationData<UserEvent> {
+    windows: Vec<AppWindow<UserEvent>>,
+    /// Handle used to open and close windows, the requests are applied after each event.
+    window_handle: Windows<UserEvent>,
     assets: assets::Assets,
+    /// Windows can only be created once the app is resumed.
+    resumed: bool,
+    /// Whether the app was already resumed once, so the first resume starts the app.
+    started: bool,
+    delegate: Box<dyn AppDelegate<UserEvent>>,
+    error_handler: Option<Box<dyn FnMut(NerfError)>>,
+    /// Error that stopped the app, returned once the event loop exits.
+    error: Option<NerfError>,
+    /// Events posted by widgets, delivered to all windows once the current event is handled.
+    posted_events: Vec<UserEvent>,
+    /// Sender of the events posted from other threads, taken when the event loop is woken for them.
+    event_sender: EventSender<UserEvent>,
+    executor: Box<dyn Executor<UserEvent>>,
+    timers: Timers,
+    next_timer_id: u64,
 }
 
-impl<UserEvent: 'static, Root: Widget<UserEvent>> winit::application::ApplicationHandler<UserEvent> for ApplicationData<UserEvent, Root> {
+impl<UserEvent: 'static> winit::application::ApplicationHandler<RuntimeEvent> for ApplicationData<UserEvent> {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        match &self.graphic_state {
-            GraphicState::ToBeCreated { window_attributes } => match event_loop.create_window(window_attributes.clone()) {
-                Ok(window) => {
-
-                    let window = std::rc::Rc::new(window);
-                    let context = softbuffer::Context::new(window.clone()).unwrap();
-                    let surface = softbuffer::Surface::new(&context, window.clone()).unwrap();
-
-                    self.graphic_state = GraphicState::Created { 
-                        window,
-                        _context: context,
-                        surface
-                    }
-                }
-                Err(error) => {
-                    println!("Failed to create window: {error}");
-                    self.graphic_state = GraphicState::Failed { error };
-                },
-            }
-            GraphicState::Created { .. } => {
-                // Maybe we have to recreate graphic events here ?
-                println!("received resume event with graphic state created!")
-            }
-            GraphicState::Failed { error } => {
-                // what to handle here ?
-                println!("received resume event with a failed graphic state (error: {error})!")
+        self.resumed = true;
+        match self.started {
+            true => self.delegate.resumed(&self.window_handle),
+            false => {
+                self.started = true;
+                self.delegate.started(&self.window_handle);
             },
         }
+        self.apply_window_requests(event_loop);
+        let results: Vec<_> = self.windows.iter_mut().map(|window| (window.id, window.create(event_loop))).collect();
+        for (id, result) in results {
+            self.check(event_loop, id, result);
+        }
     }
 
-    fn window_event(&mut self, event_loop: &winit::event_loop::ActiveEventLoop, _window_id: winit::window::WindowId, event: winit::event::WindowEvent) {
+    fn window_event(&mut self, event_loop: &winit::event_loop::ActiveEventLoop, window_id: winit::window::WindowId, event: winit::event::WindowEvent) {
+        let index = match self.windows.iter().position(|window| window.winit_id() == Some(window_id)) {
+            Some(index) => index,
+            None => return, // the window was closed, but some events were already sent for it
+        };
+        let id = self.windows[index].id;
+        let mut context = self.context_for(index);
+        let window = &mut self.windows[index];
+        let result = match event {
+            winit::event::WindowEvent::CloseRequested => {
+                if self.delegate.close_requested(id, &self.window_handle) {
+                    self.window_handle.close(id);
+                }
+                Ok(())
+            },
+            winit::event::WindowEvent::Resized(size) => window.resized(size),
+            winit::event::WindowEvent::RedrawRequested => window.redraw(&mut self.assets, &mut context),
+            other_event => {
+                if let Some(event) = AppEvent::<UserEvent>::try_from(other_event, window.scale_factor()) {
+                    window.handle_event(&event, &mut self.assets, &mut context);
+                }
+                Ok(())
+            },
+        };
+        self.finish_context(id, context);
+        self.check(event_loop, id, result);
+        self.apply_window_requests(event_loop);
+    }
+
+    fn user_event(&mut self, event_loop: &winit::event_loop::ActiveEventLoop, event: RuntimeEvent) {
         match event {
-            winit::event::WindowEvent::CloseRequested => event_loop.exit(),
-            winit::event::WindowEvent::Resized(size) => match &mut self.graphic_state {
-                GraphicState::Created { surface, window, .. } => {
-                    match (NonZeroU32::new(size.width), NonZeroU32::new(size.height)) {
-                        (Some(width), Some(height)) => {
-                            let _ = surface.resize(width, height); // todo handle error
-                            window.request_redraw()
-                        },
-                        _ => {}, // window got resized to size 0, ignore. It wont be drawn anyway.
-                    }
-                },
-                _ => {}, // window does not exist
+            RuntimeEvent::UserEvents => {
+                for event in self.event_sender.take_events() {
+                    self.dispatch_to_all(&AppEvent::Custom(event));
+                }
             },
-            winit::event::WindowEvent::RedrawRequested => self.redraw(),
-            other_event => match (&self.graphic_state, AppEvent::<UserEvent>::try_from(other_event)) {
-                (GraphicState::Created { window, .. }, Some(event)) => {
-                    let inner_size = window.inner_size();
-                    let rect = crate::Rect { x: 0, y: 0, width: NonZeroU32::new(inner_size.width).unwrap(), height: NonZeroU32::new(inner_size.height).unwrap() };
-                    let _ = self.root.handle_event(&event, rect);
+            // completed tasks are delivered with the posted events
+            RuntimeEvent::WakeTasks => self.posted_events.extend(self.executor.run_ready()),
+        }
+        self.apply_window_requests(event_loop);
+    }
 
-                },
-                _ => {}
+    fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        for (window, timer) in self.timers.take_due(std::time::Instant::now()) {
+            if let Some(index) = self.windows.iter().position(|candidate| candidate.id == window) {
+                self.dispatch(index, &AppEvent::Timer { id: timer });
             }
         }
+        // widgets can post more events and spawn more tasks while handling the posted ones
+        self.posted_events.extend(self.executor.run_ready());
+        while !self.posted_events.is_empty() {
+            for event in std::mem::take(&mut self.posted_events) {
+                self.dispatch_to_all(&AppEvent::Custom(event));
+            }
+            self.posted_events.extend(self.executor.run_ready());
+        }
+        self.apply_window_requests(event_loop);
+        // sleep until the next timer is due
+        match self.timers.next_deadline() {
+            Some(deadline) => event_loop.set_control_flow(winit::event_loop::ControlFlow::WaitUntil(deadline)),
+            None => event_loop.set_control_flow(winit::event_loop::ControlFlow::Wait),
+        }
+    }
+
+    fn suspended(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
+        // windows opened while suspended are created when the app is resumed
+        self.resumed = false;
+        self.delegate.suspended(&self.window_handle);
+    }
+
+    fn exiting(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
+        self.delegate.exiting();
     }
 }
 
-impl<UserEvent, Root: Widget<UserEvent>> ApplicationData<UserEvent, Root> {
-    pub fn redraw(&mut self) {
-        match &mut self.graphic_state {
-            GraphicState::Created { window, surface, .. } => {
-                let inner_size = window.inner_size();
-                if (inner_size.width & inner_size.height) > 0 {
-                    let rect = crate::Rect {
-                        x: 0, y: 0, width: NonZeroU32::new(inner_size.width).unwrap(), height: NonZeroU32::new(inner_size.height).unwrap(),
+impl<UserEvent: 'static> ApplicationData<UserEvent> {
+    /// Context given to the widgets of a window.
+    fn context_for(&self, index: usize) -> AppContext<UserEvent> {
+        let window = &self.windows[index];
+        let mut context = AppContext::for_window(Some(window.id), self.window_handle.clone(), window.scale_factor());
+        context.next_timer_id = self.next_timer_id;
+        context
+    }
+
+    /// Apply the requests the widgets of a window made on its context.
+    fn finish_context(&mut self, id: WindowId, mut context: AppContext<UserEvent>) {
+        if let Some(window) = self.windows.iter_mut().find(|window| window.id == id) {
+            window.apply_context(&mut context);
+        }
+        for task in context.take_tasks() {
+            self.executor.spawn(task);
+        }
+        self.next_timer_id = context.next_timer_id;
+        for request in context.timer_requests {
+            self.timers.apply(id, request);
+        }
+        self.posted_events.extend(context.posted_events);
+    }
+
+    /// Send an event to the root of a window.
+    fn dispatch(&mut self, index: usize, event: &AppEvent<UserEvent>) {
+        let id = self.windows[index].id;
+        let mut context = self.context_for(index);
+        self.windows[index].handle_event(event, &mut self.assets, &mut context);
+        self.finish_context(id, context);
+    }
+
+    /// Send an event to the roots of all windows.
+    fn dispatch_to_all(&mut self, event: &AppEvent<UserEvent>) {
+        for index in 0..self.windows.len() {
+            self.dispatch(index, event);
+        }
+    }
+
+    /// Open and close the windows that were requested with the window handle.
+    /// The app exits once there is no window left.
+    fn apply_window_requests(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        for request in self.window_handle.take_requests() {
+            match request {
+                WindowRequest::Open(mut window) => {
+                    let result = match self.resumed {
+                        true => window.create(event_loop),
+                        false => Ok(()),
                     };
-                
-                    let mut canvas = Canvas::new(
-                        &mut self.assets,
-                        surface,
-                        inner_size.width,
-                        inner_size.height,
-                    );
-                
-                    self.root.draw(&mut canvas, rect);
-                    canvas.present().unwrap();
-                }
+                    let id = window.id;
+                    self.windows.push(*window);
+                    self.check(event_loop, id, result);
+                },
+                WindowRequest::Close(id) => {
+                    self.windows.retain(|window| window.id != id);
+                    self.timers.remove_window(id);
+                },
+            }
+        }
+        if self.windows.is_empty() {
+            event_loop.exit();
+        }
+    }
+
+    /// Close a window that failed, and stop the app if it has to.
+    fn check(&mut self, event_loop: &winit::event_loop::ActiveEventLoop, id: WindowId, result: Result<(), NerfError>) {
+        if let Err(error) = result {
+            if self.close_failed(id, error) {
+                event_loop.exit();
+            }
+        }
+    }
+
+    /// Close a window that failed, and give the error to the error handler.
+    /// Returns whether the app has to stop: without an error handler the error is returned by `run`,
+    /// and with one the app stops once no window is left.
+    fn close_failed(&mut self, id: WindowId, error: NerfError) -> bool {
+        self.windows.retain(|window| window.id != id);
+        self.timers.remove_window(id);
+        match &mut self.error_handler {
+            Some(handler) => {
+                handler(error);
+                self.windows.is_empty()
+            },
+            None => {
+                self.error.get_or_insert(error);
+                true
             },
-            _ => {},
         }
     }
 }
 
+/// An app, with the windows it opens when it starts.
+///
+/// More windows can be opened and closed while the app runs, with the handle given by `windows`.
+pub struct App<UserEvent: 'static> {
+    windows: Windows<UserEvent>,
+    event_sender: EventSender<UserEvent>,
+    assets: assets::Assets,
+    delegate: Box<dyn AppDelegate<UserEvent>>,
+    executor: Box<dyn Executor<UserEvent>>,
+    error_handler: Option<Box<dyn FnMut(NerfError)>>,
+}
+
+impl<UserEvent: 'static> App<UserEvent> {
+    pub fn new() -> Self {
+        Self::with_assets(assets::Assets::new())
+    }
+
+    /// Create an app with assets that have been prepared beforehand, for example with custom fonts loaded.
+    pub fn with_assets(assets: assets::Assets) -> Self {
+        App {
+            windows: Windows::new(),
+            event_sender: EventSender::new(),
+            assets,
+            delegate: Box::new(NoDelegate),
+            executor: Box::new(LocalExecutor::new()),
+            error_handler: None,
+        }
+    }
+
+    /// Add a window that is opened when the app starts.
+    pub fn with_window<Root: Widget<UserEvent> + 'static>(self, window_attributes: winit::window::WindowAttributes, root: Root) -> Self {
+        self.windows.open(window_attributes, root);
+        self
+    }
+
+    /// Get called on the lifecycle events of the app, and decide whether windows can be closed.
+    pub fn with_delegate(self, delegate: impl AppDelegate<UserEvent> + 'static) -> Self {
+        App {
+            delegate: Box::new(delegate),
+            ..self
+        }
+    }
+
+    /// Run the tasks spawned by widgets with the given executor, instead of the default local executor.
+    pub fn with_executor(self, executor: impl Executor<UserEvent> + 'static) -> Self {
+        App {
+            executor: Box::new(executor),
+            ..self
+        }
+    }
+
+    /// Handle the errors of the windows instead of stopping the app.
+    /// A window that fails is closed, and the app keeps running with the other windows until none is left.
+    pub fn with_error_handler(self, error_handler: impl FnMut(NerfError) + 'static) -> Self {
+        App {
+            error_handler: Some(Box::new(error_handler)),
+            ..self
+        }
+    }
+
+    /// Handle to open and close windows while the app runs.
+    pub fn windows(&self) -> Windows<UserEvent> {
+        self.windows.clone()
+    }
+
+    /// Sender to post user events to the app from other threads.
+    /// Events sent before the app runs are delivered once it starts.
+    pub fn event_sender(&self) -> EventSender<UserEvent> {
+        self.event_sender.clone()
+    }
+
+    /// Run the app until all of its windows are closed, or until an error stops it.
+    pub fn run(mut self) -> Result<(), NerfError> {
+        let event_loop = winit::event_loop::EventLoop::with_user_event().build()?;
+        self.event_sender.connect(event_loop.create_proxy());
+        self.executor.set_wake_up(std::task::Waker::from(std::sync::Arc::new(TaskWakeUp(event_loop.create_proxy()))));
+
+        let mut app = ApplicationData {
+            windows: Vec::new(),
+            window_handle: self.windows,
+            assets: self.assets,
+            resumed: false,
+            started: false,
+            delegate: self.delegate,
+            error_handler: self.error_handler,
+            error: None,
+            posted_events: Vec::new(),
+            event_sender: self.event_sender.clone(),
+            executor: self.executor,
+            timers: Timers::default(),
+            next_timer_id: 0,
+        };
+
+        let result = event_loop.run_app(&mut app);
+        self.event_sender.close();
+        result?;
+        match app.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
 
-pub fn run_app<UserEvent: 'static, Root: Widget<UserEvent>>(root: Root, window_attrs: Option<winit::window::WindowAttributes>) -> Result<(), winit::error::EventLoopError> {
-    
-    let assets = assets::Assets::new();
-    let event_loop = winit::event_loop::EventLoop::with_user_event().build().unwrap();
+impl<UserEvent: 'static> Default for App<UserEvent> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+pub fn run_app<UserEvent: 'static, Root: Widget<UserEvent> + 'static>(root: Root, window_attrs: Option<winit::window::WindowAttributes>) -> Result<(), NerfError> {
+    run_app_with_assets(root, window_attrs, assets::Assets::new())
+}
+
+/// Run the app with assets that have been prepared beforehand, for example with custom fonts loaded.
+pub fn run_app_with_assets<UserEvent: 'static, Root: Widget<UserEvent> + 'static>(
+    root: Root,
+    window_attrs: Option<winit::window::WindowAttributes>,
+    assets: assets::Assets,
+) -> Result<(), NerfError> {
 
     let window_attributes = match window_attrs {
         Some(attrs) => attrs,
         None => winit::window::WindowAttributes::default()
             .with_name("Nerf", "Nerf"),
     };
-    
-    let mut app = ApplicationData {
-        _m: core::marker::PhantomData,
-        graphic_state: GraphicState::ToBeCreated { window_attributes },
-        root,
-        assets,
+
+    App::with_assets(assets).with_window(window_attributes, root).run()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::{ApplicationData, NerfError};
+    use crate::{
+        app::{assets::Assets, delegate::NoDelegate, event_sender::EventSender, executor::LocalExecutor, timers::Timers, windows::{WindowRequest, Windows}},
+        Empty,
     };
 
-    event_loop.run_app(&mut app)
+    fn app(error_handler: Option<Box<dyn FnMut(NerfError)>>) -> ApplicationData<()> {
+        ApplicationData {
+            windows: Vec::new(),
+            window_handle: Windows::new(),
+            assets: Assets::new(),
+            resumed: false,
+            started: false,
+            delegate: Box::new(NoDelegate),
+            error_handler,
+            error: None,
+            posted_events: Vec::new(),
+            event_sender: EventSender::new(),
+            executor: Box::new(LocalExecutor::new()),
+            timers: Timers::default(),
+            next_timer_id: 0,
+        }
+    }
+
+    fn open_windows(app: &mut ApplicationData<()>, count: usize) -> Vec<super::WindowId> {
+        let ids = (0..count).map(|_| app.window_handle.open(winit::window::WindowAttributes::default(), Empty::expand())).collect();
+        for request in app.window_handle.take_requests() {
+            if let WindowRequest::Open(window) = request {
+                app.windows.push(*window);
+            }
+        }
+        ids
+    }
+
+    fn error() -> NerfError {
+        NerfError::Surface(softbuffer::SoftBufferError::Unimplemented)
+    }
+
+    #[test]
+    fn handled_errors_stop_the_app_once_no_window_is_left() {
+        let handled = Rc::new(Cell::new(0));
+        let counter = handled.clone();
+        let mut app = app(Some(Box::new(move |_| counter.set(counter.get() + 1))));
+        let ids = open_windows(&mut app, 2);
+        assert!(!app.close_failed(ids[0], error()));
+        assert_eq!(app.windows.len(), 1);
+        assert!(app.close_failed(ids[1], error()));
+        assert!(app.windows.is_empty());
+        assert_eq!(handled.get(), 2);
+        assert!(app.error.is_none());
+    }
+
+    #[test]
+    fn unhandled_errors_stop_the_app() {
+        let mut app = app(None);
+        let ids = open_windows(&mut app, 2);
+        assert!(app.close_failed(ids[0], error()));
+        assert!(matches!(app.error, Some(NerfError::Surface(_))));
+    }
 }
@@ -1,13 +1,163 @@
+use std::{future::Future, time::{Duration, Instant}};
 
+use winit::window::CursorIcon;
 
-pub struct AppContext {
-    
+use super::{executor::Task, timers::TimerRequest, window::WindowId, windows::Windows};
+
+/// Identifier of a timer, given when it is scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(pub(crate) u64);
+
+/// Runtime services given to widgets while they draw and handle events.
+///
+/// Requests made on the context are applied by the app to the window of the widget, once the draw or the event is done.
+pub struct AppContext<UserEvent = ()> {
+    pub(crate) window: Option<WindowId>,
+    pub(crate) windows: Windows<UserEvent>,
+    pub(crate) scale_factor: f64,
+    /// Time of the frame being drawn, or of the event being handled.
+    pub(crate) frame_time: Instant,
+    pub(crate) redraw_requested: bool,
+    pub(crate) layout_requested: bool,
+    pub(crate) cursor_icon: Option<CursorIcon>,
+    pub(crate) title: Option<String>,
+    pub(crate) posted_events: Vec<UserEvent>,
+    pub(crate) next_timer_id: u64,
+    /// Timers started and cancelled during this call, in order.
+    pub(crate) timer_requests: Vec<TimerRequest>,
+    /// Tasks spawned during this call, given to the executor of the app.
+    pub(crate) tasks: Vec<Task<UserEvent>>,
 }
 
-impl AppContext {
+impl<UserEvent: 'static> AppContext<UserEvent> {
+    /// Create a context that is not attached to any window, for example to drive widgets in tests and benchmarks.
+    /// Requests made on it are only recorded.
     pub fn new() -> Self {
-        Self {
-            
+        Self::for_window(None, Windows::new(), 1.0)
+    }
+
+    /// Use the given time as the frame time, for example to step through animations in tests.
+    pub fn with_frame_time(self, frame_time: Instant) -> Self {
+        AppContext { frame_time, ..self }
+    }
+
+    pub(crate) fn for_window(window: Option<WindowId>, windows: Windows<UserEvent>, scale_factor: f64) -> Self {
+        AppContext {
+            window,
+            windows,
+            scale_factor,
+            frame_time: Instant::now(),
+            redraw_requested: false,
+            layout_requested: false,
+            cursor_icon: None,
+            title: None,
+            posted_events: Vec::new(),
+            next_timer_id: 0,
+            timer_requests: Vec::new(),
+            tasks: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl<UserEvent: 'static> Default for AppContext<UserEvent> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<UserEvent> AppContext<UserEvent> {
+    /// Ask for the window to be drawn again, even if no widget responded to an event.
+    pub fn request_redraw(&mut self) {
+        self.redraw_requested = true;
+    }
+
+    /// Whether a redraw was requested on this context.
+    pub fn redraw_requested(&self) -> bool {
+        self.redraw_requested
+    }
+
+    /// Ask for the whole window to be laid out again and redrawn.
+    ///
+    /// Layout results are kept until the window is resized or a widget calls this.
+    /// Widgets that change their children or their size requirements, for example when text is set,
+    /// items are added or the scroll offset moves the visible items, call this so their ancestors make room for them.
+    pub fn request_layout(&mut self) {
+        self.layout_requested = true;
+        self.redraw_requested = true;
+    }
+
+    /// Whether a layout was requested on this context.
+    pub fn layout_requested(&self) -> bool {
+        self.layout_requested
+    }
+
+    /// Change the cursor icon shown over the window.
+    pub fn set_cursor_icon(&mut self, cursor_icon: CursorIcon) {
+        self.cursor_icon = Some(cursor_icon);
+    }
+
+    /// Change the title of the window.
+    pub fn set_title(&mut self, title: impl Into<String>) {
+        self.title = Some(title.into());
+    }
+
+    /// Number of physical pixels per logical pixel of the window.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Time of the frame being drawn, or of the event being handled.
+    /// It is the same for all widgets of a frame, so animations started together stay in sync.
+    pub fn frame_time(&self) -> Instant {
+        self.frame_time
+    }
+
+    /// Identifier of the window the widget is in, or None if the context is not attached to a window.
+    pub fn window_id(&self) -> Option<WindowId> {
+        self.window
+    }
+
+    /// Handle to open and close the windows of the app.
+    pub fn windows(&self) -> &Windows<UserEvent> {
+        &self.windows
+    }
+
+    /// Send an event to the root widget of every window, as `AppEvent::Custom`.
+    /// The event is delivered once the current draw or event is done.
+    pub fn post_event(&mut self, event: UserEvent) {
+        self.posted_events.push(event);
+    }
+
+    /// Schedule an `AppEvent::Timer` event with the returned id, sent to the window of the widget after the delay.
+    pub fn set_timeout(&mut self, delay: Duration) -> TimerId {
+        self.start_timer(delay, None)
+    }
+
+    /// Schedule an `AppEvent::Timer` event with the returned id, sent to the window of the widget every period
+    /// until the timer is cancelled or the window is closed. Periods shorter than a millisecond are rounded up to it.
+    pub fn set_interval(&mut self, period: Duration) -> TimerId {
+        self.start_timer(period, Some(period))
+    }
+
+    /// Stop a timer, so no more events are sent for it. Cancelling a timer that already fired does nothing.
+    pub fn cancel_timer(&mut self, id: TimerId) {
+        self.timer_requests.push(TimerRequest::Cancel(id));
+    }
+
+    fn start_timer(&mut self, delay: Duration, interval: Option<Duration>) -> TimerId {
+        let id = TimerId(self.next_timer_id);
+        self.next_timer_id += 1;
+        self.timer_requests.push(TimerRequest::Start { id, deadline: Instant::now() + delay, interval });
+        id
+    }
+
+    /// Run a future on the executor of the app, without blocking the widget.
+    /// Its output is sent to the root widget of every window as `AppEvent::Custom` once it completes.
+    pub fn spawn(&mut self, future: impl Future<Output = UserEvent> + 'static) {
+        self.tasks.push(Box::pin(future));
+    }
+
+    pub(crate) fn take_tasks(&mut self) -> Vec<Task<UserEvent>> {
+        std::mem::take(&mut self.tasks)
+    }
+}
@@ -30,4 +30,10 @@ impl Assets {
     pub fn text_mut(&mut self) -> &mut self::text_assets::TextAssets {
         &mut self.text
     }
-}
\ No newline at end of file
+}
+
+impl Default for Assets {
+    fn default() -> Self {
+        Assets::new()
+    }
+}
@@ -1,8 +1,40 @@
+use std::path::Path;
 
+use crate::FontFamily;
 
+
+/// Errors that can happen when loading a font into the text assets.
+#[derive(Debug)]
+pub enum FontLoadingError {
+    /// The font file could not be read.
+    Io(std::io::Error),
+    /// The data was read, but no font face could be parsed from it.
+    NoFaceFound,
+}
+
+impl std::fmt::Display for FontLoadingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FontLoadingError::Io(error) => write!(f, "unable to read font file: {error}"),
+            FontLoadingError::NoFaceFound => write!(f, "no font face found in font data"),
+        }
+    }
+}
+
+impl std::error::Error for FontLoadingError {}
+
+impl From<std::io::Error> for FontLoadingError {
+    fn from(error: std::io::Error) -> Self {
+        FontLoadingError::Io(error)
+    }
+}
+
+/// Text rendering assets: the loaded fonts, and the glyph cache.
 pub struct TextAssets {
     font_system: cosmic_text::FontSystem,
     swash_cache: cosmic_text::SwashCache,
+    /// Families to use, in order, when a named family is not available.
+    family_fallbacks: Vec<FontFamily>,
 }
 
 
@@ -11,10 +43,90 @@ impl TextAssets {
         TextAssets {
             font_system: cosmic_text::FontSystem::new(),
             swash_cache: cosmic_text::SwashCache::new(),
+            family_fallbacks: Vec::new(),
         }
     }
 
     pub fn fonts_and_cache(&mut self) -> (&mut cosmic_text::FontSystem, &mut cosmic_text::SwashCache) {
         (&mut self.font_system, &mut self.swash_cache)
     }
-}
\ No newline at end of file
+
+    /// Register a font from raw data, that is bundled with the application.
+    /// This is meant to be used with `include_bytes!`, so the font is available on any machine.
+    /// Returns the names of the families that have been loaded, that can be used with `FontFamily::Name`.
+    pub fn load_font_bytes(&mut self, data: &'static [u8]) -> Result<Vec<String>, FontLoadingError> {
+        self.load_font_source(cosmic_text::fontdb::Source::Binary(std::sync::Arc::new(data)))
+    }
+
+    /// Register a font from a file.
+    /// Returns the names of the families that have been loaded, that can be used with `FontFamily::Name`.
+    pub fn load_font_file<P: AsRef<Path>>(&mut self, path: P) -> Result<Vec<String>, FontLoadingError> {
+        let data = std::fs::read(path)?;
+        self.load_font_source(cosmic_text::fontdb::Source::Binary(std::sync::Arc::new(data)))
+    }
+
+    fn load_font_source(&mut self, source: cosmic_text::fontdb::Source) -> Result<Vec<String>, FontLoadingError> {
+        let database = self.font_system.db_mut();
+        let ids = database.load_font_source(source);
+        let mut families = Vec::new();
+        for face in ids.iter().filter_map(|id| database.face(*id)) {
+            for (family, _) in face.families.iter() {
+                if !families.contains(family) {
+                    families.push(family.clone());
+                }
+            }
+        }
+        match families.is_empty() {
+            true => Err(FontLoadingError::NoFaceFound),
+            false => Ok(families),
+        }
+    }
+
+    /// Set the font family used for a generic family (serif, sans serif, monospace, cursive or fantasy).
+    /// Setting it for a named family does nothing.
+    pub fn set_default_family<S: Into<String>>(&mut self, generic_family: FontFamily, family_name: S) {
+        let database = self.font_system.db_mut();
+        match generic_family {
+            FontFamily::Name(_) => {},
+            FontFamily::Serif => database.set_serif_family(family_name),
+            FontFamily::SansSerif => database.set_sans_serif_family(family_name),
+            FontFamily::Monospace => database.set_monospace_family(family_name),
+            FontFamily::Cursive => database.set_cursive_family(family_name),
+            FontFamily::Fantasy => database.set_fantasy_family(family_name),
+        }
+    }
+
+    /// Set the families that are tried, in order, when a named family is not available.
+    /// The first available one is used, so it is a good idea to end the list with a generic family.
+    pub fn set_family_fallbacks(&mut self, family_fallbacks: Vec<FontFamily>) {
+        self.family_fallbacks = family_fallbacks;
+    }
+
+    /// Whether the family can be found in the loaded fonts. Generic families are always available.
+    pub fn is_family_available(&self, family: &FontFamily) -> bool {
+        match family {
+            FontFamily::Name(name) => self.font_system.db().faces().any(
+                |face| face.families.iter().any(|(family, _)| family == name)
+            ),
+            _ => true,
+        }
+    }
+
+    /// Get the family that will actually be used to draw text with the given family, using the fallbacks if it is not available.
+    pub fn resolve_family(&self, family: &FontFamily) -> FontFamily {
+        // looking up the family goes through all the loaded faces, skip it when there is nothing to fall back to.
+        if self.family_fallbacks.is_empty() || self.is_family_available(family) {
+            return family.clone();
+        }
+        self.family_fallbacks.iter()
+            .find(|fallback| self.is_family_available(fallback))
+            .unwrap_or(family)
+            .clone()
+    }
+}
+
+impl Default for TextAssets {
+    fn default() -> Self {
+        TextAssets::new()
+    }
+}
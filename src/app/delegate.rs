@@ -0,0 +1,30 @@
+use super::{window::WindowId, windows::Windows};
+
+/// Callbacks for the lifecycle of the app.
+///
+/// All methods have a default implementation that does nothing, so only the ones that are needed are implemented.
+/// The windows handle can be used to open or close windows from the callbacks, for example to ask the user to save their work.
+pub trait AppDelegate<UserEvent> {
+    /// The app started, and its first windows are being created.
+    fn started(&mut self, _windows: &Windows<UserEvent>) {}
+
+    /// The user asked to close the window, for example with the close button of the title bar.
+    /// Return false to keep the window open, for example when there are unsaved changes.
+    fn close_requested(&mut self, _window: WindowId, _windows: &Windows<UserEvent>) -> bool {
+        true
+    }
+
+    /// The app was suspended by the os, and no frame will be shown until it is resumed.
+    fn suspended(&mut self, _windows: &Windows<UserEvent>) {}
+
+    /// The app was resumed after being suspended.
+    fn resumed(&mut self, _windows: &Windows<UserEvent>) {}
+
+    /// The app is exiting, after all windows are closed or after an error.
+    fn exiting(&mut self) {}
+}
+
+/// Delegate used when the app has none: windows are closed when requested.
+pub(crate) struct NoDelegate;
+
+impl<UserEvent> AppDelegate<UserEvent> for NoDelegate {}
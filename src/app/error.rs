@@ -0,0 +1,48 @@
+
+
+/// Errors that can happen while running the app.
+#[derive(Debug)]
+pub enum NerfError {
+    /// The event loop could not be created, or stopped with an error.
+    EventLoop(winit::error::EventLoopError),
+    /// The os refused to create a window.
+    WindowCreation(winit::error::OsError),
+    /// The drawing surface of a window could not be created or resized.
+    Surface(softbuffer::SoftBufferError),
+    /// A drawn frame could not be shown in its window.
+    Presentation(softbuffer::SoftBufferError),
+}
+
+impl std::fmt::Display for NerfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NerfError::EventLoop(error) => write!(f, "event loop error: {error}"),
+            NerfError::WindowCreation(error) => write!(f, "unable to create window: {error}"),
+            NerfError::Surface(error) => write!(f, "unable to set up window surface: {error}"),
+            NerfError::Presentation(error) => write!(f, "unable to present frame: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for NerfError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            NerfError::EventLoop(error) => Some(error),
+            NerfError::WindowCreation(error) => Some(error),
+            NerfError::Surface(error) => Some(error),
+            NerfError::Presentation(error) => Some(error),
+        }
+    }
+}
+
+impl From<winit::error::EventLoopError> for NerfError {
+    fn from(error: winit::error::EventLoopError) -> Self {
+        NerfError::EventLoop(error)
+    }
+}
+
+impl From<winit::error::OsError> for NerfError {
+    fn from(error: winit::error::OsError) -> Self {
+        NerfError::WindowCreation(error)
+    }
+}
@@ -4,9 +4,10 @@
 
 use std::path::PathBuf;
 
-use winit::{event::{AxisId, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, Touch, TouchPhase}, window::Theme};
+use winit::{dpi::PhysicalPosition, event::{AxisId, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, Touch, TouchPhase}, window::Theme};
 
 use self::mouse_position::MousePosition;
+use super::app_context::TimerId;
 
 pub(crate) mod mouse_position;
 
@@ -76,13 +77,14 @@ pub enum AppEvent<CustomEvent> {
 
     /// The cursor has moved on the window.
     CursorMoved {
-        /// (x,y) coords in pixels relative to the top-left corner of the window. Because the range of this data is
+        /// (x,y) coords in logical pixels relative to the top-left corner of the window. Because the range of this data is
         /// limited by the display area and it may have been transformed by the OS to implement effects such as cursor
         /// acceleration, it should not be used to implement non-cursor-like interactions such as 3D camera control.
         position: MousePosition,
     },
 
     /// A mouse wheel movement or touchpad scroll occurred.
+    /// Pixel deltas are in logical pixels.
     MouseWheel {
         delta: MouseScrollDelta,
         phase: TouchPhase,
@@ -155,13 +157,19 @@ pub enum AppEvent<CustomEvent> {
         value: f64,
     },
 
-    /// Touch event has been received
+    /// Touch event has been received. Its location is in logical pixels.
     ///
     /// ## Platform-specific
     ///
     /// - **macOS:** Unsupported.
     Touch(Touch),
 
+    /// The scale factor of the window changed, for example because it was moved to another monitor.
+    /// The window is laid out and drawn again with the new scale factor.
+    ScaleFactorChanged {
+        scale_factor: f64,
+    },
+
     /// The system window theme has changed.
     ///
     /// Applications might wish to react to this to change the theme of the content of the window
@@ -181,12 +189,18 @@ pub enum AppEvent<CustomEvent> {
     /// - **iOS / Android / Web / Wayland / Windows / Orbital:** Unsupported.
     Occluded(bool),
 
+    /// A timer started with `AppContext::set_timeout` or `AppContext::set_interval` is due.
+    Timer {
+        id: TimerId,
+    },
+
     /// Custom event. This is a generic type.
     Custom(CustomEvent),
 }
 
 impl<CustomEvent> AppEvent<CustomEvent> {
-    pub fn try_from(raw_event: winit::event::WindowEvent) -> Option<AppEvent<CustomEvent>> {
+    /// Convert a winit event, with positions in the physical pixels of a window with the given scale factor.
+    pub fn try_from(raw_event: winit::event::WindowEvent, scale_factor: f64) -> Option<AppEvent<CustomEvent>> {
         match raw_event {
             winit::event::WindowEvent::DroppedFile(path) => Some(AppEvent::DroppedFile(path)),
             winit::event::WindowEvent::HoveredFile(path) => Some(AppEvent::HoveredFile(path)),
@@ -195,12 +209,20 @@ impl<CustomEvent> AppEvent<CustomEvent> {
             winit::event::WindowEvent::KeyboardInput { is_synthetic, .. } => Some(AppEvent::KeyboardInput { is_synthetic }),
             winit::event::WindowEvent::ModifiersChanged(modifiers) => Some(AppEvent::ModifiersChanged(modifiers)),
             winit::event::WindowEvent::Ime(ime) => Some(AppEvent::Ime(ime)),
-            winit::event::WindowEvent::CursorMoved { position, .. } => Some(AppEvent::CursorMoved { position: MousePosition::from(position) }),
+            winit::event::WindowEvent::CursorMoved { position, .. } => Some(AppEvent::CursorMoved { position: MousePosition::from(position.to_logical(scale_factor)) }),
             winit::event::WindowEvent::CursorLeft { .. } => Some(AppEvent::CursorMoved { position: MousePosition::OutOfWindow }),
-            winit::event::WindowEvent::MouseWheel { delta, phase, .. } => Some(AppEvent::MouseWheel { delta, phase }),
+            winit::event::WindowEvent::MouseWheel { delta, phase, .. } => {
+                let delta = match delta {
+                    // the delta keeps its type, but is converted like any other position
+                    MouseScrollDelta::PixelDelta(position) => MouseScrollDelta::PixelDelta(to_logical_values(position, scale_factor)),
+                    line_delta => line_delta,
+                };
+                Some(AppEvent::MouseWheel { delta, phase })
+            },
             winit::event::WindowEvent::MouseInput { state, button, .. } => Some(AppEvent::MouseInput { state, button }),
             winit::event::WindowEvent::AxisMotion { axis, value, .. } => Some(AppEvent::AxisMotion { axis, value }),
-            winit::event::WindowEvent::Touch(touch) => Some(AppEvent::Touch(touch)),
+            winit::event::WindowEvent::Touch(touch) => Some(AppEvent::Touch(Touch { location: to_logical_values(touch.location, scale_factor), ..touch })),
+            winit::event::WindowEvent::ScaleFactorChanged { scale_factor, .. } => Some(AppEvent::ScaleFactorChanged { scale_factor }),
             winit::event::WindowEvent::ThemeChanged(theme) => Some(AppEvent::ThemeChanged(theme)),
             winit::event::WindowEvent::Occluded(occluded) => Some(AppEvent::Occluded(occluded)),
             _ => None,
@@ -208,6 +230,12 @@ impl<CustomEvent> AppEvent<CustomEvent> {
     }
 }
 
+/// Divide a physical position by the scale factor, for winit events that only carry physical positions.
+fn to_logical_values(position: PhysicalPosition<f64>, scale_factor: f64) -> PhysicalPosition<f64> {
+    let logical = position.to_logical::<f64>(scale_factor);
+    PhysicalPosition::new(logical.x, logical.y)
+}
+
 impl<CustomEvent> From<CustomEvent> for AppEvent<CustomEvent> {
     fn from(event: CustomEvent) -> Self {
         AppEvent::Custom(event)
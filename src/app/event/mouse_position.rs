@@ -1,28 +1,27 @@
-use winit::dpi::PhysicalPosition;
+use winit::dpi::LogicalPosition;
 
+use crate::{Point, Rect};
 
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+
+/// Position of the mouse, in logical pixels.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MousePosition {
     OutOfWindow,
-    InWindow(u32, u32),
+    InWindow(Point),
 }
 
-impl From<PhysicalPosition<f64>> for MousePosition {
-    fn from(value: PhysicalPosition<f64>) -> Self {
-        // is this safe enough ? all values are integers, and we are sure they are positive.
-        // it's unlikely we'll have any value bigger than u32::MAX.
-        MousePosition::InWindow(value.x as u32, value.y as u32)
+impl From<LogicalPosition<f64>> for MousePosition {
+    fn from(value: LogicalPosition<f64>) -> Self {
+        MousePosition::InWindow(Point::new(value.x as f32, value.y as f32))
     }
 }
 
 impl MousePosition {
-    pub fn is_in_rect(&self, rect: softbuffer::Rect) -> bool {
+    pub fn is_in_rect(&self, rect: Rect) -> bool {
         match self {
             MousePosition::OutOfWindow => false,
-            MousePosition::InWindow(x, y) => {
-                x >= &rect.x && x < &(rect.x + rect.width.get()) && y >= &rect.y && y < &(rect.y + rect.height.get())
-            }
+            MousePosition::InWindow(point) => rect.contains(*point),
         }
     }
-}
\ No newline at end of file
+}
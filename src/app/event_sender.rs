@@ -0,0 +1,144 @@
+use std::{sync::{Arc, Mutex}, task::Wake};
+
+use winit::event_loop::EventLoopProxy;
+
+/// Events sent to the event loop of the app.
+/// They hold no user event, so the proxies can be sent to other threads whatever the user event type is.
+pub(crate) enum RuntimeEvent {
+    /// User events were sent with an `EventSender`, and wait to be taken from it.
+    UserEvents,
+    /// A task of the executor was woken, and the executor needs to run.
+    WakeTasks,
+}
+
+/// Wakes the app when a task of the executor is woken, from any thread.
+pub(crate) struct TaskWakeUp(pub(crate) EventLoopProxy<RuntimeEvent>);
+
+impl Wake for TaskWakeUp {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // if the app exited, there are no tasks to run anymore
+        let _ = self.0.send_event(RuntimeEvent::WakeTasks);
+    }
+}
+
+enum SenderState<UserEvent> {
+    /// The app is not running yet, events are kept until it starts.
+    Pending(Vec<UserEvent>),
+    /// Events are kept until the app, woken by the proxy, takes them.
+    Running {
+        events: Vec<UserEvent>,
+        proxy: EventLoopProxy<RuntimeEvent>,
+    },
+    /// The app exited, events can not be delivered anymore.
+    Closed,
+}
+
+/// Sends user events to the app from any thread.
+///
+/// Events are delivered to the root widget of every window as `AppEvent::Custom`.
+/// The sender can be cloned and sent to other threads, as long as the user event type can.
+pub struct EventSender<UserEvent> {
+    state: Arc<Mutex<SenderState<UserEvent>>>,
+}
+
+impl<UserEvent> Clone for EventSender<UserEvent> {
+    fn clone(&self) -> Self {
+        EventSender { state: self.state.clone() }
+    }
+}
+
+impl<UserEvent> EventSender<UserEvent> {
+    pub(crate) fn new() -> Self {
+        EventSender {
+            state: Arc::new(Mutex::new(SenderState::Pending(Vec::new()))),
+        }
+    }
+
+    /// Send an event to the app. Events sent before the app runs are delivered when it starts.
+    /// If the app already exited, the event is given back.
+    pub fn send(&self, event: UserEvent) -> Result<(), UserEvent> {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match &mut *state {
+            SenderState::Pending(events) => {
+                events.push(event);
+                Ok(())
+            },
+            SenderState::Running { events, proxy } => match proxy.send_event(RuntimeEvent::UserEvents) {
+                // the app takes the events with the lock, so it can not miss this one
+                Ok(()) => {
+                    events.push(event);
+                    Ok(())
+                },
+                Err(_) => Err(event),
+            },
+            SenderState::Closed => Err(event),
+        }
+    }
+
+    /// Connect the sender to the running event loop, and wake it for the events that were waiting for it.
+    pub(crate) fn connect(&self, proxy: EventLoopProxy<RuntimeEvent>) {
+        let mut state = self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let events = match std::mem::replace(&mut *state, SenderState::Closed) {
+            SenderState::Pending(events) => events,
+            _ => Vec::new(),
+        };
+        if !events.is_empty() {
+            // the event loop is not running yet, so it can not be closed
+            let _ = proxy.send_event(RuntimeEvent::UserEvents);
+        }
+        *state = SenderState::Running { events, proxy };
+    }
+
+    /// Take the events sent since the last call, in the order they were sent.
+    pub(crate) fn take_events(&self) -> Vec<UserEvent> {
+        match &mut *self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) {
+            SenderState::Running { events, .. } => std::mem::take(events),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The app exited, following events are given back to the senders.
+    pub(crate) fn close(&self) {
+        *self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = SenderState::Closed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventSender, SenderState};
+
+    #[test]
+    fn events_wait_for_the_app_to_run() {
+        let sender: EventSender<u32> = EventSender::new();
+        assert_eq!(sender.clone().send(1), Ok(()));
+        assert_eq!(sender.send(2), Ok(()));
+        let state = sender.state.lock().unwrap();
+        match &*state {
+            SenderState::Pending(events) => assert_eq!(events, &vec![1, 2]),
+            _ => panic!("the sender is not connected"),
+        }
+    }
+
+    #[test]
+    fn events_are_given_back_once_the_app_exited() {
+        let sender: EventSender<u32> = EventSender::new();
+        let clone = sender.clone();
+        sender.close();
+        assert_eq!(sender.send(1), Err(1));
+        assert_eq!(clone.send(2), Err(2));
+    }
+
+    #[test]
+    fn sender_is_send_when_the_events_are() {
+        let sender: EventSender<u32> = EventSender::new();
+        let result = std::thread::spawn(move || {
+            sender.close();
+            sender.send(3)
+        }).join().unwrap();
+        assert_eq!(result, Err(3));
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+};
+
+/// A task spawned by a widget. Its output is sent to the windows as `AppEvent::Custom` once it completes.
+pub type Task<UserEvent> = Pin<Box<dyn Future<Output = UserEvent>>>;
+
+/// Runs the tasks spawned by widgets with `AppContext::spawn`.
+///
+/// Tasks are polled on the thread of the app, so they can hold widget data that is not `Send`.
+/// Work that blocks should be moved to another thread, and its result awaited in the task.
+pub trait Executor<UserEvent> {
+    /// Called once when the app starts. Waking this waker makes the app call `run_ready` soon after.
+    fn set_wake_up(&mut self, wake_up: Waker);
+    /// Start running a task.
+    fn spawn(&mut self, task: Task<UserEvent>);
+    /// Poll the tasks that can make progress, without blocking, and return the outputs of the ones that completed.
+    fn run_ready(&mut self) -> Vec<UserEvent>;
+}
+
+/// Tasks that were woken and need to be polled again.
+struct ReadyQueue {
+    ready: Mutex<Vec<usize>>,
+    wake_up: Mutex<Option<Waker>>,
+}
+
+/// Waker of a task, that puts it back in the ready queue and wakes the app.
+struct TaskWaker {
+    index: usize,
+    queue: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.queue.ready.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(self.index);
+        if let Some(wake_up) = &*self.queue.wake_up.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) {
+            wake_up.wake_by_ref();
+        }
+    }
+}
+
+/// The default executor, that polls the tasks on the thread of the app when they are woken.
+pub struct LocalExecutor<UserEvent> {
+    /// Tasks by index, None once they completed so the index can be reused.
+    tasks: Vec<Option<Task<UserEvent>>>,
+    queue: Arc<ReadyQueue>,
+}
+
+impl<UserEvent> LocalExecutor<UserEvent> {
+    pub fn new() -> Self {
+        LocalExecutor {
+            tasks: Vec::new(),
+            queue: Arc::new(ReadyQueue { ready: Mutex::new(Vec::new()), wake_up: Mutex::new(None) }),
+        }
+    }
+
+    /// Number of tasks that did not complete yet.
+    pub fn pending_tasks(&self) -> usize {
+        self.tasks.iter().filter(|task| task.is_some()).count()
+    }
+}
+
+impl<UserEvent> Default for LocalExecutor<UserEvent> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<UserEvent> Executor<UserEvent> for LocalExecutor<UserEvent> {
+    fn set_wake_up(&mut self, wake_up: Waker) {
+        *self.queue.wake_up.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(wake_up);
+    }
+
+    fn spawn(&mut self, task: Task<UserEvent>) {
+        let index = match self.tasks.iter().position(Option::is_none) {
+            Some(index) => {
+                self.tasks[index] = Some(task);
+                index
+            },
+            None => {
+                self.tasks.push(Some(task));
+                self.tasks.len() - 1
+            },
+        };
+        // new tasks are polled once, they will be polled again when woken
+        self.queue.ready.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(index);
+    }
+
+    fn run_ready(&mut self) -> Vec<UserEvent> {
+        let mut outputs = Vec::new();
+        loop {
+            let ready = std::mem::take(&mut *self.queue.ready.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+            if ready.is_empty() {
+                return outputs;
+            }
+            for index in ready {
+                // a task can be woken several times before it is polled, or after it completed
+                let task = match self.tasks.get_mut(index) {
+                    Some(Some(task)) => task,
+                    _ => continue,
+                };
+                let waker = Waker::from(Arc::new(TaskWaker { index, queue: self.queue.clone() }));
+                if let Poll::Ready(output) = task.as_mut().poll(&mut Context::from_waker(&waker)) {
+                    self.tasks[index] = None;
+                    outputs.push(output);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        future::Future,
+        pin::Pin,
+        rc::Rc,
+        sync::{atomic::{AtomicUsize, Ordering}, Arc},
+        task::{Context, Poll, Wake, Waker},
+    };
+
+    use super::{Executor, LocalExecutor};
+    use crate::{AppContext, AppEvent, Canvas, Rect, Widget, WidgetSizeRequirement};
+
+    /// A value that is set later, like the result of a computation on another thread.
+    struct Slot {
+        value: Option<u32>,
+        waker: Option<Waker>,
+    }
+
+    struct SlotFuture(Rc<RefCell<Slot>>);
+
+    impl Future for SlotFuture {
+        type Output = u32;
+        fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<u32> {
+            let mut slot = self.0.borrow_mut();
+            match slot.value {
+                Some(value) => Poll::Ready(value),
+                None => {
+                    slot.waker = Some(context.waker().clone());
+                    Poll::Pending
+                },
+            }
+        }
+    }
+
+    fn set(slot: &Rc<RefCell<Slot>>, value: u32) {
+        let waker = {
+            let mut slot = slot.borrow_mut();
+            slot.value = Some(value);
+            slot.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    struct CountingWaker(AtomicUsize);
+
+    impl Wake for CountingWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn ready_task_completes_on_first_run() {
+        let mut executor = LocalExecutor::new();
+        executor.spawn(Box::pin(async { 42 }));
+        assert_eq!(executor.run_ready(), vec![42]);
+        assert_eq!(executor.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn pending_task_completes_once_woken() {
+        let wake_ups = Arc::new(CountingWaker(AtomicUsize::new(0)));
+        let mut executor = LocalExecutor::new();
+        executor.set_wake_up(Waker::from(wake_ups.clone()));
+        let slot = Rc::new(RefCell::new(Slot { value: None, waker: None }));
+        executor.spawn(Box::pin(SlotFuture(slot.clone())));
+
+        assert!(executor.run_ready().is_empty());
+        assert_eq!(executor.pending_tasks(), 1);
+        assert_eq!(wake_ups.0.load(Ordering::SeqCst), 0);
+
+        set(&slot, 7);
+        // waking the task wakes the app, that runs the executor again
+        assert_eq!(wake_ups.0.load(Ordering::SeqCst), 1);
+        assert_eq!(executor.run_ready(), vec![7]);
+        assert_eq!(executor.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn task_can_await_other_futures() {
+        let slot = Rc::new(RefCell::new(Slot { value: None, waker: None }));
+        let mut executor = LocalExecutor::new();
+        let future = SlotFuture(slot.clone());
+        executor.spawn(Box::pin(async move { future.await * 2 }));
+        executor.spawn(Box::pin(async { 1 }));
+        assert_eq!(executor.run_ready(), vec![1]);
+        set(&slot, 21);
+        assert_eq!(executor.run_ready(), vec![42]);
+    }
+
+    /// Starts loading when it receives an event, and shows the result once it is back.
+    struct Loader {
+        slot: Rc<RefCell<Slot>>,
+        loaded: Option<u32>,
+    }
+
+    impl Widget<u32> for Loader {
+        type EventResponse = ();
+        fn draw(&self, _canvas: &mut Canvas, _rect: Rect, _context: &mut AppContext<u32>) {}
+        fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+            (WidgetSizeRequirement::None, WidgetSizeRequirement::None)
+        }
+        fn handle_event(&mut self, event: &AppEvent<u32>, _rect: Rect, context: &mut AppContext<u32>) {
+            match event {
+                AppEvent::Focused(true) => context.spawn(SlotFuture(self.slot.clone())),
+                AppEvent::Custom(value) => self.loaded = Some(*value),
+                _ => {},
+            }
+        }
+    }
+
+    #[test]
+    fn widget_spawns_task_and_gets_its_result() {
+        let rect = Rect::new(0., 0., 1., 1.);
+        let slot = Rc::new(RefCell::new(Slot { value: None, waker: None }));
+        let mut loader = Loader { slot: slot.clone(), loaded: None };
+        let mut executor = LocalExecutor::new();
+
+        // the app gives the tasks spawned on the context to the executor
+        let mut context = AppContext::new();
+        loader.handle_event(&AppEvent::Focused(true), rect, &mut context);
+        for task in context.take_tasks() {
+            executor.spawn(task);
+        }
+        assert!(executor.run_ready().is_empty());
+
+        set(&slot, 3);
+        for output in executor.run_ready() {
+            loader.handle_event(&AppEvent::Custom(output), rect, &mut AppContext::new());
+        }
+        assert_eq!(loader.loaded, Some(3));
+    }
+}
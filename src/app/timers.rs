@@ -0,0 +1,137 @@
+use std::time::{Duration, Instant};
+
+use super::{app_context::TimerId, window::WindowId};
+
+/// Shortest period of an interval, so a zero period does not keep the app busy firing it.
+pub(crate) const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Timer changes asked by a widget on its context.
+pub(crate) enum TimerRequest {
+    Start {
+        id: TimerId,
+        deadline: Instant,
+        /// Period of the timer if it repeats.
+        interval: Option<Duration>,
+    },
+    Cancel(TimerId),
+}
+
+struct ScheduledTimer {
+    id: TimerId,
+    /// Window of the widget that started the timer, that receives its events.
+    window: WindowId,
+    deadline: Instant,
+    interval: Option<Duration>,
+}
+
+/// The timers started by the widgets of all windows.
+#[derive(Default)]
+pub(crate) struct Timers {
+    scheduled: Vec<ScheduledTimer>,
+}
+
+impl Timers {
+    pub(crate) fn apply(&mut self, window: WindowId, request: TimerRequest) {
+        match request {
+            TimerRequest::Start { id, deadline, interval } => {
+                let interval = interval.map(|interval| interval.max(MIN_INTERVAL));
+                self.scheduled.push(ScheduledTimer { id, window, deadline, interval });
+            },
+            TimerRequest::Cancel(id) => self.scheduled.retain(|timer| timer.id != id),
+        }
+    }
+
+    /// Remove the timers of a window that was closed.
+    pub(crate) fn remove_window(&mut self, window: WindowId) {
+        self.scheduled.retain(|timer| timer.window != window);
+    }
+
+    /// Take the timers that are due, with the window they are sent to.
+    /// Intervals are scheduled again for their next period.
+    pub(crate) fn take_due(&mut self, now: Instant) -> Vec<(WindowId, TimerId)> {
+        let mut due = Vec::new();
+        self.scheduled.retain_mut(|timer| {
+            if timer.deadline > now {
+                return true;
+            }
+            due.push((timer.window, timer.id));
+            match timer.interval {
+                Some(interval) => {
+                    // if the app could not keep up, skip the missed ticks instead of sending them all at once
+                    timer.deadline += interval;
+                    if timer.deadline <= now {
+                        timer.deadline = now + interval;
+                    }
+                    true
+                },
+                None => false,
+            }
+        });
+        due
+    }
+
+    /// When the next timer is due, to know how long the app can sleep.
+    pub(crate) fn next_deadline(&self) -> Option<Instant> {
+        self.scheduled.iter().map(|timer| timer.deadline).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{TimerRequest, Timers, MIN_INTERVAL};
+    use crate::{app::app_context::TimerId, WindowId};
+
+    const WINDOW: WindowId = WindowId(0);
+
+    fn start(timers: &mut Timers, id: u64, deadline: Instant, interval: Option<Duration>) {
+        timers.apply(WINDOW, TimerRequest::Start { id: TimerId(id), deadline, interval });
+    }
+
+    #[test]
+    fn timeout_fires_once() {
+        let now = Instant::now();
+        let mut timers = Timers::default();
+        start(&mut timers, 0, now + Duration::from_millis(10), None);
+        assert!(timers.take_due(now).is_empty());
+        assert_eq!(timers.next_deadline(), Some(now + Duration::from_millis(10)));
+        assert_eq!(timers.take_due(now + Duration::from_millis(10)), vec![(WINDOW, TimerId(0))]);
+        assert!(timers.take_due(now + Duration::from_secs(1)).is_empty());
+        assert_eq!(timers.next_deadline(), None);
+    }
+
+    #[test]
+    fn interval_fires_every_period() {
+        let now = Instant::now();
+        let period = Duration::from_millis(100);
+        let mut timers = Timers::default();
+        start(&mut timers, 0, now + period, Some(period));
+        assert_eq!(timers.take_due(now + period).len(), 1);
+        assert_eq!(timers.next_deadline(), Some(now + period * 2));
+        // ticks missed while the app was busy are skipped
+        assert_eq!(timers.take_due(now + period * 5).len(), 1);
+        assert_eq!(timers.next_deadline(), Some(now + period * 6));
+    }
+
+    #[test]
+    fn cancelled_timers_do_not_fire() {
+        let now = Instant::now();
+        let mut timers = Timers::default();
+        start(&mut timers, 0, now, None);
+        start(&mut timers, 1, now, Some(Duration::from_millis(100)));
+        timers.apply(WINDOW, TimerRequest::Cancel(TimerId(1)));
+        assert_eq!(timers.take_due(now), vec![(WINDOW, TimerId(0))]);
+        assert_eq!(timers.next_deadline(), None);
+    }
+
+    #[test]
+    fn zero_interval_waits_the_minimum_period() {
+        let now = Instant::now();
+        let mut timers = Timers::default();
+        start(&mut timers, 0, now, Some(Duration::ZERO));
+        assert_eq!(timers.take_due(now).len(), 1);
+        assert_eq!(timers.next_deadline(), Some(now + MIN_INTERVAL));
+        assert!(timers.take_due(now).is_empty());
+    }
+}
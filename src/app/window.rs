@@ -0,0 +1,189 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    geometry::constraints::SizeConstraints,
+    utils::nonable::Nonable,
+    widget::{layout::LayoutContext, Widget},
+    AppEvent,
+    Canvas,
+    Size,
+};
+
+use super::{app_context::AppContext, assets::Assets, error::NerfError};
+
+/// Identifier of a window of the app, given when the window is opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowId(pub(crate) u64);
+
+/// Root widget of a window, with its event response erased.
+/// This allows windows with different root widgets to live in the same app.
+pub(crate) trait WindowRoot<UserEvent> {
+    fn draw(&self, canvas: &mut Canvas, rect: crate::Rect, context: &mut AppContext<UserEvent>);
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size;
+    /// Handle the event, and return whether the root responded to it.
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: crate::Rect, context: &mut AppContext<UserEvent>) -> bool;
+}
+
+impl<UserEvent, Root: Widget<UserEvent>> WindowRoot<UserEvent> for Root {
+    fn draw(&self, canvas: &mut Canvas, rect: crate::Rect, context: &mut AppContext<UserEvent>) {
+        Widget::draw(self, canvas, rect, context)
+    }
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        Widget::layout(self, constraints, context)
+    }
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: crate::Rect, context: &mut AppContext<UserEvent>) -> bool {
+        !Widget::handle_event(self, event, rect, context).is_none()
+    }
+}
+
+pub(crate) enum GraphicState {
+    Created {
+        window: std::rc::Rc<winit::window::Window>,
+        _context: softbuffer::Context<std::rc::Rc<winit::window::Window>>,
+        surface: softbuffer::Surface<std::rc::Rc<winit::window::Window>, std::rc::Rc<winit::window::Window>>,
+    },
+    ToBeCreated {
+        window_attributes: winit::window::WindowAttributes
+    },
+}
+
+/// A window of the app, with its own root widget, surface and size.
+pub(crate) struct AppWindow<UserEvent> {
+    pub(crate) id: WindowId,
+    graphic_state: GraphicState,
+    root: Box<dyn WindowRoot<UserEvent>>,
+    /// Window size the tree was last laid out with, or None if the tree might have changed since.
+    layout_size: Option<Size>,
+}
+
+impl<UserEvent> AppWindow<UserEvent> {
+    pub(crate) fn new(id: WindowId, window_attributes: winit::window::WindowAttributes, root: Box<dyn WindowRoot<UserEvent>>) -> Self {
+        AppWindow {
+            id,
+            graphic_state: GraphicState::ToBeCreated { window_attributes },
+            root,
+            layout_size: None,
+        }
+    }
+
+    /// Create the os window and its surface, if it was not created yet.
+    pub(crate) fn create(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) -> Result<(), NerfError> {
+        if let GraphicState::ToBeCreated { window_attributes } = &self.graphic_state {
+            let window = std::rc::Rc::new(event_loop.create_window(window_attributes.clone())?);
+            let context = softbuffer::Context::new(window.clone()).map_err(NerfError::Surface)?;
+            let surface = softbuffer::Surface::new(&context, window.clone()).map_err(NerfError::Surface)?;
+
+            self.graphic_state = GraphicState::Created {
+                window,
+                _context: context,
+                surface
+            }
+        }
+        Ok(())
+    }
+
+    /// Winit identifier of the os window, once it is created.
+    pub(crate) fn winit_id(&self) -> Option<winit::window::WindowId> {
+        match &self.graphic_state {
+            GraphicState::Created { window, .. } => Some(window.id()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn resized(&mut self, size: winit::dpi::PhysicalSize<u32>) -> Result<(), NerfError> {
+        // a window resized to size 0 is ignored, it wont be drawn anyway.
+        if let (GraphicState::Created { surface, window, .. }, Some(width), Some(height)) =
+            (&mut self.graphic_state, NonZeroU32::new(size.width), NonZeroU32::new(size.height))
+        {
+            surface.resize(width, height).map_err(NerfError::Surface)?;
+            window.request_redraw();
+        }
+        Ok(())
+    }
+
+    /// Rect of the whole window in logical pixels, or None if the window is not created or has no size.
+    fn rect(&self) -> Option<crate::Rect> {
+        match &self.graphic_state {
+            GraphicState::Created { window, .. } => {
+                let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
+                let rect = crate::Rect::from_size(Size::new(inner_size.width, inner_size.height));
+                (!rect.is_empty()).then_some(rect)
+            },
+            _ => None,
+        }
+    }
+
+    /// Run the layout pass on the whole tree for the window rect, unless it was already done with this size.
+    fn layout(&mut self, assets: &mut Assets, rect: crate::Rect) {
+        let size = rect.size();
+        if self.layout_size != Some(size) {
+            let mut context = LayoutContext::new(assets);
+            self.root.layout(SizeConstraints::tight_for(rect), &mut context);
+            self.layout_size = Some(size);
+        }
+    }
+
+    /// Number of physical pixels per logical pixel of the window.
+    pub(crate) fn scale_factor(&self) -> f64 {
+        match &self.graphic_state {
+            GraphicState::Created { window, .. } => window.scale_factor(),
+            GraphicState::ToBeCreated { .. } => 1.0,
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, event: &AppEvent<UserEvent>, assets: &mut Assets, context: &mut AppContext<UserEvent>) {
+        if let AppEvent::ScaleFactorChanged { .. } = event {
+            // the logical size of the window may be the same, but text and borders are drawn with the new scale
+            self.layout_size = None;
+            context.request_redraw();
+        }
+        if let Some(rect) = self.rect() {
+            self.layout(assets, rect);
+            // a widget responding to an event most likely changed how it looks, so it is drawn again.
+            // the layout is kept: widgets that change their size ask for a new one with `AppContext::request_layout`
+            if self.root.handle_event(event, rect, context) {
+                context.request_redraw();
+            }
+        }
+    }
+
+    /// Apply the requests the widgets made on the context to the os window.
+    pub(crate) fn apply_context(&mut self, context: &mut AppContext<UserEvent>) {
+        if context.layout_requested {
+            self.layout_size = None;
+        }
+        if let GraphicState::Created { window, .. } = &self.graphic_state {
+            if context.redraw_requested {
+                window.request_redraw();
+            }
+            if let Some(cursor_icon) = context.cursor_icon.take() {
+                window.set_cursor(cursor_icon);
+            }
+            if let Some(title) = context.title.take() {
+                window.set_title(&title);
+            }
+        }
+    }
+
+    pub(crate) fn redraw(&mut self, assets: &mut Assets, context: &mut AppContext<UserEvent>) -> Result<(), NerfError> {
+        if let Some(rect) = self.rect() {
+            // the layout is only computed when the tree or the window changed, and reused by the draw and the following events
+            self.layout(assets, rect);
+            if let GraphicState::Created { surface, window, .. } = &mut self.graphic_state {
+                // the surface is in physical pixels, the canvas converts the logical rects of the widgets to it
+                let surface_size = window.inner_size();
+                let mut canvas = Canvas::new(
+                    assets,
+                    surface,
+                    surface_size.width,
+                    surface_size.height,
+                    window.scale_factor(),
+                ).map_err(NerfError::Presentation)?;
+
+                self.root.draw(&mut canvas, rect, context);
+                canvas.present().map_err(NerfError::Presentation)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::Widget;
+
+use super::window::{AppWindow, WindowId};
+
+pub(crate) enum WindowRequest<UserEvent> {
+    Open(Box<AppWindow<UserEvent>>),
+    Close(WindowId),
+}
+
+pub(crate) struct WindowRequests<UserEvent> {
+    next_id: u64,
+    pub(crate) requests: Vec<WindowRequest<UserEvent>>,
+}
+
+/// Handle to open and close the windows of the app.
+///
+/// The handle can be cloned and kept by widgets, to open dialogs, tool palettes or detached panels at runtime.
+/// Requests are applied by the app once the current event is handled.
+pub struct Windows<UserEvent> {
+    requests: Rc<RefCell<WindowRequests<UserEvent>>>,
+}
+
+impl<UserEvent> Clone for Windows<UserEvent> {
+    fn clone(&self) -> Self {
+        Windows { requests: self.requests.clone() }
+    }
+}
+
+impl<UserEvent: 'static> Windows<UserEvent> {
+    pub(crate) fn new() -> Self {
+        Windows {
+            requests: Rc::new(RefCell::new(WindowRequests { next_id: 0, requests: Vec::new() })),
+        }
+    }
+
+    /// Open a new window with the given root widget.
+    pub fn open<Root: Widget<UserEvent> + 'static>(&self, window_attributes: winit::window::WindowAttributes, root: Root) -> WindowId {
+        let mut requests = self.requests.borrow_mut();
+        let id = WindowId(requests.next_id);
+        requests.next_id += 1;
+        requests.requests.push(WindowRequest::Open(Box::new(AppWindow::new(id, window_attributes, Box::new(root)))));
+        id
+    }
+
+    /// Close the given window. When all windows are closed, the app exits.
+    pub fn close(&self, id: WindowId) {
+        self.requests.borrow_mut().requests.push(WindowRequest::Close(id));
+    }
+
+    /// Take the requests that were made since the last call.
+    pub(crate) fn take_requests(&self) -> Vec<WindowRequest<UserEvent>> {
+        std::mem::take(&mut self.requests.borrow_mut().requests)
+    }
+}
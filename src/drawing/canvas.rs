@@ -1,33 +1,92 @@
-
-
-use crate::{Color, app::assets::Assets, BorderType};
+use crate::{Color, app::assets::Assets, BorderType, Rect};
 
 /// If the text features is enabled, implements text drawing operations.
 #[cfg(feature = "text")]
 pub(crate) mod text;
 
+/// A rect snapped to the physical pixels of the surface, given by its edges.
+/// Edges can be out of the surface, for content that is scrolled out of view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PixelRect {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl PixelRect {
+    /// Position in logical pixels, snapped to the closest physical pixel edge.
+    fn snap(position: f32, scale_factor: f64) -> i64 {
+        (position as f64 * scale_factor).round() as i64
+    }
+
+    /// Convert a rect in logical pixels to physical pixels.
+    /// Edges are snapped on their own, so rects that touch still touch once converted.
+    /// Rects that are not empty keep at least one pixel, so thin lines do not disappear.
+    fn from_logical(rect: Rect, scale_factor: f64) -> PixelRect {
+        let (left, top) = (Self::snap(rect.x, scale_factor), Self::snap(rect.y, scale_factor));
+        let (right, bottom) = (Self::snap(rect.right(), scale_factor), Self::snap(rect.bottom(), scale_factor));
+        match rect.is_empty() {
+            true => PixelRect { left, top, right: left, bottom: top },
+            false => PixelRect { left, top, right: right.max(left + 1), bottom: bottom.max(top + 1) },
+        }
+    }
+
+    fn width(&self) -> i64 {
+        (self.right - self.left).max(0)
+    }
+
+    fn height(&self) -> i64 {
+        (self.bottom - self.top).max(0)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.right <= self.left || self.bottom <= self.top
+    }
+
+    /// The part of the rect that is also in the other one.
+    fn intersect(&self, other: PixelRect) -> PixelRect {
+        PixelRect {
+            left: self.left.max(other.left),
+            top: self.top.max(other.top),
+            right: self.right.min(other.right),
+            bottom: self.bottom.min(other.bottom),
+        }
+    }
+}
+
 pub struct Canvas<'a> {
     assets: Option<&'a mut Assets>,
     buffer: softbuffer::Buffer<'a, std::rc::Rc<winit::window::Window>, std::rc::Rc<winit::window::Window>>,
     surface_width: u32,
     surface_height: u32,
+    /// Number of physical pixels of the surface per logical pixel.
+    scale_factor: f64,
+    /// Area of the surface that can be drawn on. Anything outside of it is discarded.
+    clip: PixelRect,
 }
 
 /// A canvas is a buffer that can be drawn on.
+///
+/// Everything is drawn with logical pixels, that are converted to the physical pixels of the surface with the scale factor.
+/// Rects are not rounded before that: they are snapped to the physical pixels when they are rasterized.
 impl<'a> Canvas<'a> {
     pub fn new(
         assets: &'a mut Assets,
         surface: &'a mut softbuffer::Surface<std::rc::Rc<winit::window::Window>, std::rc::Rc<winit::window::Window>>,
         surface_width: u32,
         surface_height: u32,
-    ) -> Canvas<'a> {
-        let buffer = surface.buffer_mut().unwrap();
-        Canvas {
+        scale_factor: f64,
+    ) -> Result<Canvas<'a>, softbuffer::SoftBufferError> {
+        let buffer = surface.buffer_mut()?;
+        Ok(Canvas {
             assets: Some(assets),
             buffer,
             surface_width,
             surface_height,
-        }
+            scale_factor,
+            clip: PixelRect { left: 0, top: 0, right: surface_width as i64, bottom: surface_height as i64 },
+        })
     }
 
     pub fn buffer(self) -> softbuffer::Buffer<'a, std::rc::Rc<winit::window::Window>, std::rc::Rc<winit::window::Window>> {
@@ -38,6 +97,39 @@ impl<'a> Canvas<'a> {
         self.buffer.present()
     }
 
+    /// Number of physical pixels of the surface per logical pixel.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// Convert a length in logical pixels to physical pixels, without rounding it.
+    fn to_physical_length(&self, length: f32) -> f32 {
+        (length as f64 * self.scale_factor) as f32
+    }
+
+    /// Convert a rect in logical pixels to physical pixels.
+    fn to_physical(&self, rect: Rect) -> PixelRect {
+        PixelRect::from_logical(rect, self.scale_factor)
+    }
+
+    /// Draw with anything outside of the given rect discarded, for example to show the visible part of scrolled content.
+    /// Nested clips can only reduce the drawable area.
+    pub fn with_clip(&mut self, clip: Rect, draw: impl FnOnce(&mut Self)) {
+        let previous_clip = self.clip;
+        self.clip = self.visible_area(self.to_physical(clip));
+        draw(self);
+        self.clip = previous_clip;
+    }
+
+    pub fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.fill_physical_rect(self.to_physical(rect), color);
+    }
+
+    /// Get the part of the rect in physical pixels that is in the clip area.
+    fn visible_area(&self, rect: PixelRect) -> PixelRect {
+        rect.intersect(self.clip)
+    }
+
 
 }
 
@@ -45,16 +137,20 @@ impl<'a> Canvas<'a> {
 #[cfg(feature = "skia")]
 impl<'a> Canvas<'a> {
     
-    fn softbuffer_to_skia_rect(rect: softbuffer::Rect) -> tiny_skia::Rect {
-        tiny_skia::Rect::from_xywh(
-            rect.x as f32,
-            rect.y as f32,
-            rect.width.get() as f32,
-            rect.height.get() as f32,
-        ).unwrap()
+    fn pixel_to_skia_rect(rect: PixelRect) -> Option<tiny_skia::Rect> {
+        tiny_skia::Rect::from_ltrb(
+            rect.left as f32,
+            rect.top as f32,
+            rect.right as f32,
+            rect.bottom as f32,
+        )
     }
 
-    pub fn fill_rect(&mut self, rect: softbuffer::Rect, color: Color) {
+    fn fill_physical_rect(&mut self, rect: PixelRect, color: Color) {
+        let rect = match Self::pixel_to_skia_rect(self.visible_area(rect)) {
+            Some(rect) => rect,
+            None => return, // nothing to draw
+        };
         // todo : create a single skia pixmap with canvas creation, and reuse it.
         let slice = unsafe {
             std::slice::from_raw_parts_mut(
@@ -68,7 +164,7 @@ impl<'a> Canvas<'a> {
             self.surface_height,
         ).unwrap();
         skia_pixmap.fill_rect(
-            Self::softbuffer_to_skia_rect(rect),
+            rect,
             &Color::into(color),
             tiny_skia::Transform::identity(),
             None,
@@ -77,14 +173,20 @@ impl<'a> Canvas<'a> {
 
     pub fn fill_decorated(
         &mut self,
-        rect: softbuffer::Rect,
+        rect: Rect,
         fill_color: Option<Color>,
         border_color: Option<Color>,
         border_type: BorderType,
-        corner_radius: u32,
-        border_width: u32,
+        corner_radius: f32,
+        border_width: f32,
     ) {
-        let mut border_width = border_width;
+        let pixel_rect = self.to_physical(rect);
+        let mut border_width = self.to_physical_length(border_width);
+        let corner_radius = self.to_physical_length(corner_radius);
+        let visible_area = self.visible_area(pixel_rect);
+        if visible_area.is_empty() {
+            return;
+        }
         let slice = unsafe {
             std::slice::from_raw_parts_mut(
                 self.buffer.as_mut_ptr() as *mut u8,
@@ -97,21 +199,42 @@ impl<'a> Canvas<'a> {
             self.surface_height,
         ).unwrap();
         // build the path !
+        let physical_rect = Rect::new(
+            pixel_rect.left as f32,
+            pixel_rect.top as f32,
+            pixel_rect.width() as f32,
+            pixel_rect.height() as f32,
+        );
         let mut path = tiny_skia::PathBuilder::new();
-        border_type.build_path(&mut path, rect, &mut border_width, corner_radius);
-        let path = path.finish().unwrap();
+        border_type.build_path(&mut path, physical_rect, &mut border_width, corner_radius);
+        let path = match path.finish() {
+            Some(path) => path,
+            None => return,
+        };
+
+        // the path stays within the rect, so only mask it when it is partially hidden
+        let mask = match visible_area != pixel_rect {
+            true => {
+                let mut mask = tiny_skia::Mask::new(self.surface_width, self.surface_height).unwrap();
+                if let Some(clip_rect) = Self::pixel_to_skia_rect(visible_area) {
+                    mask.fill_path(&tiny_skia::PathBuilder::from_rect(clip_rect), tiny_skia::FillRule::Winding, false, tiny_skia::Transform::identity());
+                }
+                Some(mask)
+            },
+            false => None,
+        };
 
         if let Some(color) = border_color {
             let paint = color.into();
             let stroke = tiny_skia::Stroke {
-                width: border_width as f32,
+                width: border_width,
                 ..Default::default()
             };
-            skia_pixmap.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), None);
+            skia_pixmap.stroke_path(&path, &paint, &stroke, tiny_skia::Transform::identity(), mask.as_ref());
         }
         if let Some(color) = fill_color {
             let paint = color.into();
-            skia_pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), None);
+            skia_pixmap.fill_path(&path, &paint, tiny_skia::FillRule::Winding, tiny_skia::Transform::identity(), mask.as_ref());
         }
 
     }
@@ -130,13 +253,46 @@ impl<'a> Canvas<'a> {
         self.buffer[(y * self.surface_width + x) as usize] = color;
     }
 
-    pub fn fill_rect(&mut self, rect: softbuffer::Rect, color: Color) {
+    fn fill_physical_rect(&mut self, rect: PixelRect, color: Color) {
+        // the clip is always in the surface, so the visible area is too
+        let rect = self.visible_area(rect);
         let color = color.value();
-        for y in rect.y..rect.y + rect.height.get() {
-            for x in rect.x..rect.x + rect.width.get() {
-                self.draw_pixel(x, y, color);
+        for y in rect.top..rect.top + rect.height() {
+            for x in rect.left..rect.left + rect.width() {
+                self.draw_pixel(x as u32, y as u32, color);
             }
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::PixelRect;
+    use crate::Rect;
+
+    #[test]
+    fn touching_rects_still_touch_once_snapped() {
+        for scale_factor in [1.0, 1.25, 1.5, 1.75, 2.0] {
+            let mut x = 0.3;
+            for width in [10.4, 7.7, 0.6, 13.1] {
+                let left = PixelRect::from_logical(Rect::new(x, 0., width, 10.), scale_factor);
+                let right = PixelRect::from_logical(Rect::new(x + width, 0., 5., 10.), scale_factor);
+                assert_eq!(left.right, right.left, "rects at {x} with scale factor {scale_factor}");
+                x += width;
+            }
+        }
+    }
+
+    #[test]
+    fn thin_rects_keep_a_pixel() {
+        let line = PixelRect::from_logical(Rect::new(10.2, 5., 0.2, 0.1), 1.0);
+        assert_eq!((line.width(), line.height()), (1, 1));
+        let line = PixelRect::from_logical(Rect::new(3., 3.4, 100., 0.3), 1.5);
+        assert_eq!((line.left, line.right, line.height()), (5, 155, 1));
+    }
+
+    #[test]
+    fn empty_rects_stay_empty() {
+        assert!(PixelRect::from_logical(Rect::new(10., 10., 0., 20.), 2.0).is_empty());
+    }
+}
@@ -1,54 +1,411 @@
-use std::num::NonZeroU32;
+use std::collections::HashMap;
 
-use crate::{Canvas, TextStyle};
+use crate::{app::assets::Assets, Canvas, Color, DecorationLine, DecorationStyle, Rect, Size, SpanStyle, TextDecoration, TextSpan, TextStyle};
 
+use super::PixelRect;
 
+/// Character used to replace the text overflow with the ellipsis overflow modes.
+const ELLIPSIS: &str = "…";
 
+/// Font metrics used to place decoration lines, scaled to the font size.
+/// Offsets are distances from the baseline to the top of the line, going up.
+#[derive(Debug, Clone, Copy)]
+struct DecorationMetrics {
+    underline_offset: f32,
+    strikeout_offset: f32,
+    ascent: f32,
+    stroke_size: f32,
+}
+
+impl DecorationMetrics {
+    fn from_font(font_system: &mut cosmic_text::FontSystem, font_id: cosmic_text::fontdb::ID, font_size: f32) -> DecorationMetrics {
+        match font_system.get_font(font_id) {
+            Some(font) => {
+                let metrics = font.as_swash().metrics(&[]).scale(font_size);
+                DecorationMetrics {
+                    underline_offset: metrics.underline_offset,
+                    strikeout_offset: metrics.strikeout_offset,
+                    ascent: metrics.ascent,
+                    // some fonts do not provide a stroke size
+                    stroke_size: if metrics.stroke_size > 0.0 { metrics.stroke_size } else { font_size / 14.0 },
+                }
+            },
+            // rough values that look right for most fonts
+            None => DecorationMetrics {
+                underline_offset: -font_size * 0.1,
+                strikeout_offset: font_size * 0.3,
+                ascent: font_size * 0.8,
+                stroke_size: font_size / 14.0,
+            },
+        }
+    }
+}
+
+/// A glyph that has decoration lines, with its position in the text buffer.
+struct DecoratedGlyph {
+    span_index: usize,
+    font_id: cosmic_text::fontdb::ID,
+    font_size: f32,
+    x: f32,
+    width: f32,
+    baseline: f32,
+    color: Color,
+}
+
+
+/// Text as it was laid out by the last draw, to find which character is at a given position.
+pub(crate) struct TextLayout {
+    buffer: cosmic_text::Buffer,
+    /// Offset of the text from the top of the rect, caused by the vertical alignment.
+    y_offset: f32,
+    text_height: f32,
+    /// The text is laid out in physical pixels, while positions are given in logical pixels.
+    scale_factor: f32,
+}
+
+impl TextLayout {
+    /// Get the cursor at the given position, relative to the top left of the rect the text was drawn in.
+    /// Positions outside of the text are moved to the closest character.
+    pub(crate) fn hit(&self, x: f32, y: f32) -> Option<cosmic_text::Cursor> {
+        let (x, y) = (x * self.scale_factor, y * self.scale_factor);
+        // below the last line, the buffer finds nothing, so stay in the text.
+        let y = (y - self.y_offset).min(self.text_height - 1.0);
+        self.buffer.hit(x, y)
+    }
+}
 
 impl<'a> Canvas<'a> {
-    pub fn draw_text(&mut self, text: &str, rect: softbuffer::Rect, style: &TextStyle) {
-        // Text metrics indicate the font size and line height of a buffer
-        // note to myself : line height is the space between the top of the buffer and the baseline of the text,
-        // then, it is also the space between the baseline of the text and the baseline of the next line.
-        let metrics = cosmic_text::Metrics::new(style.size, style.size + style.additional_interline);
+    pub fn draw_text(&mut self, text: &str, rect: Rect, style: &TextStyle) {
+        self.draw_spans(std::iter::once((text, &SpanStyle::new())), rect, style, None);
+    }
+
+    pub fn draw_rich_text(&mut self, spans: &[TextSpan], rect: Rect, style: &TextStyle) {
+        self.draw_spans(spans.iter().map(|span| (span.text.as_str(), &span.style)), rect, style, None);
+    }
+
+    /// Draw text with the characters between the two cursors highlighted.
+    /// Returns the layout of the text, to hit test against it.
+    pub(crate) fn draw_selectable_text(
+        &mut self,
+        text: &str,
+        rect: Rect,
+        style: &TextStyle,
+        selection: Option<(cosmic_text::Cursor, cosmic_text::Cursor, Color)>,
+    ) -> TextLayout {
+        self.draw_spans(std::iter::once((text, &SpanStyle::new())), rect, style, selection)
+    }
+
+    fn draw_spans<'s>(
+        &mut self,
+        spans: impl Iterator<Item = (&'s str, &'s SpanStyle)> + Clone,
+        rect: Rect,
+        style: &TextStyle,
+        selection: Option<(cosmic_text::Cursor, cosmic_text::Cursor, Color)>,
+    ) -> TextLayout {
+        // the text is laid out and drawn in physical pixels, so it is rendered sharp at any scale factor
+        let rect = self.to_physical(rect);
+        let scale_factor = self.scale_factor as f32;
         // the option is a hack for now, because the draw closure requires another access to self.
         // this will decouple the assets from the canvas
         let assets = self.assets.take().unwrap();
+        // the span index is stored in the attributes metadata, to find back the span style of each glyph.
+        let span_styles: Vec<&SpanStyle> = spans.clone().map(|(_, span_style)| span_style).collect();
+        let mut text_buffer = layout_spans(assets, spans, style, Some(rect.width() as f32), Some(rect.height() as f32), scale_factor);
         let (font_system, swash_cache) = assets.text_mut().fonts_and_cache();
 
-        // A Buffer provides shaping and layout for a UTF-8 string, create one per text widget
-        let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
-
         // Borrow buffer together with the font system for more convenient method calls
-        let mut buffer = buffer.borrow_with(font_system);
+        let mut buffer = text_buffer.borrow_with(font_system);
 
-        // Set a size for the text buffer, in pixels
-        buffer.set_size(rect.width.get() as f32, rect.height.get() as f32);
-        buffer.set_wrap(style.overflow.into());
-
-        // Add some text!
-        let attrs = cosmic_text::Attrs::new();
-        buffer.set_text(text, attrs, cosmic_text::Shaping::Basic);
+        // the buffer always starts drawing at its top, so vertical alignment is an offset on the whole text.
+        let text_height: f32 = buffer.layout_runs().map(|run| run.line_height).sum();
+        // the offset is rounded, so the glyphs stay aligned on the pixels they were rasterized for
+        let remaining_height = (rect.height() as f32 - text_height).max(0.0);
+        let y_offset = style.vertical_align.get_top_space(remaining_height).round();
 
-        /*
-        
-         */
-
-        // Perform shaping as desired
-        buffer.shape_until_scroll(); // todo what is this for ? works fine without
+        // highlights and selection are drawn behind the glyphs
+        if let Some((selection_start, selection_end, color)) = selection {
+            for run in buffer.layout_runs() {
+                if let Some((x, width)) = run.highlight(selection_start, selection_end) {
+                    self.fill_text_area(rect, x, run.line_top + y_offset, width, run.line_height, color);
+                }
+            }
+        }
+        for run in buffer.layout_runs() {
+            for glyph in run.glyphs.iter() {
+                if let Some(color) = span_styles.get(glyph.metadata).and_then(|span_style| span_style.highlight) {
+                    self.fill_text_area(rect, glyph.x, run.line_top + y_offset, glyph.w, run.line_height, color);
+                }
+            }
+        }
 
         // Draw the buffer (for performance, instead use SwashCache directly)
         // todo : improve perf using the above indication
+        let y_offset_pixels = y_offset as i64;
         buffer.draw(swash_cache, style.color.into(), |x, y, w, h, color| {
-            match (NonZeroU32::new(w), NonZeroU32::new(h), u32::try_from(x), u32::try_from(y)) {
-                (Some(width), Some(height), Ok(x), Ok(y)) => {
-                    let rect_to_draw = softbuffer::Rect { x: rect.x + x, y: rect.y + y, width, height };
-                    self.fill_rect(rect_to_draw, color.into());
+            let left = rect.left + x as i64;
+            let top = rect.top + y as i64 + y_offset_pixels;
+            let glyph_rect = PixelRect { left, top, right: left + w as i64, bottom: top + h as i64 };
+            // anything outside of the rect is clipped, as the buffer does not clip horizontal overflow.
+            self.fill_physical_rect(glyph_rect.intersect(rect), color.into());
+        });
+
+        // decoration lines are drawn on top of the glyphs.
+        // collect the glyphs first, as the font metrics are needed and the buffer holds the font system.
+        let decorated_glyphs: Vec<DecoratedGlyph> = buffer.layout_runs().flat_map(|run| {
+            run.glyphs.iter().map(move |glyph| DecoratedGlyph {
+                span_index: glyph.metadata,
+                font_id: glyph.font_id,
+                font_size: glyph.font_size,
+                x: glyph.x,
+                width: glyph.w,
+                baseline: run.line_y + y_offset,
+                color: glyph.color_opt.map(Color::from).unwrap_or(style.color),
+            })
+        }).filter(|glyph| {
+            !style.decorations.is_empty()
+                || span_styles.get(glyph.span_index).map(|span_style| !span_style.decorations.is_empty()).unwrap_or(false)
+        }).collect();
+
+        // consecutive glyphs mostly share the same font, so only recompute the metrics when it changes
+        let mut last_metrics: Option<(cosmic_text::fontdb::ID, f32, DecorationMetrics)> = None;
+        for glyph in decorated_glyphs.iter() {
+            let metrics = match last_metrics {
+                Some((font_id, font_size, metrics)) if font_id == glyph.font_id && font_size == glyph.font_size => metrics,
+                _ => {
+                    let metrics = DecorationMetrics::from_font(font_system, glyph.font_id, glyph.font_size);
+                    last_metrics = Some((glyph.font_id, glyph.font_size, metrics));
+                    metrics
                 },
-                _ => {},
+            };
+            let span_decorations = span_styles.get(glyph.span_index).map(|span_style| span_style.decorations.as_slice()).unwrap_or(&[]);
+            for decoration in style.decorations.iter().chain(span_decorations.iter()) {
+                self.draw_decoration(rect, glyph, &metrics, decoration);
             }
-        });
+        }
 
         self.assets = Some(assets);
+
+        TextLayout {
+            buffer: text_buffer,
+            y_offset,
+            text_height,
+            scale_factor,
+        }
+    }
+
+    /// Draw a decoration line along a glyph.
+    /// Patterns are computed from the position in the text, so they continue seamlessly from one glyph to the next.
+    fn draw_decoration(&mut self, rect: PixelRect, glyph: &DecoratedGlyph, metrics: &DecorationMetrics, decoration: &TextDecoration) {
+        let color = decoration.color.unwrap_or(glyph.color);
+        // the font metrics are already scaled with the font size, but the thickness is given in logical pixels
+        let thickness = decoration.thickness.map(|thickness| thickness * self.scale_factor as f32).unwrap_or(metrics.stroke_size).max(1.0);
+        let top = match decoration.line {
+            DecorationLine::Underline => glyph.baseline - metrics.underline_offset,
+            DecorationLine::Strikethrough => glyph.baseline - metrics.strikeout_offset,
+            DecorationLine::Overline => glyph.baseline - metrics.ascent,
+        };
+        let (left, right) = (glyph.x, glyph.x + glyph.width);
+        match decoration.style {
+            DecorationStyle::Solid => self.fill_text_area(rect, left, top, glyph.width, thickness, color),
+            DecorationStyle::Dotted => {
+                // square dots, separated by the same amount of space
+                let period = thickness * 2.0;
+                let mut dot_x = (left / period).floor() * period;
+                while dot_x < right {
+                    let (dot_left, dot_right) = (dot_x.max(left), (dot_x + thickness).min(right));
+                    if dot_right > dot_left {
+                        self.fill_text_area(rect, dot_left, top, dot_right - dot_left, thickness, color);
+                    }
+                    dot_x += period;
+                }
+            },
+            DecorationStyle::Wavy => {
+                // the wave goes below the line position, so it does not overlap the glyphs
+                let amplitude = thickness;
+                let wavelength = thickness * 4.0;
+                let wave = |x: f32| top + amplitude + amplitude * (x * std::f32::consts::TAU / wavelength).sin();
+                let mut column = left.floor();
+                while column < right {
+                    // each column covers the wave between its two edges, so steep parts have no holes
+                    let (y_start, y_end) = (wave(column), wave(column + 1.0));
+                    let column_top = y_start.min(y_end);
+                    let height = (y_start - y_end).abs() + thickness;
+                    let (column_left, column_right) = (column.max(left), (column + 1.0).min(right));
+                    self.fill_text_area(rect, column_left, column_top, column_right - column_left, height, color);
+                    column += 1.0;
+                }
+            },
+        }
+    }
+
+    /// Fill an area given in text buffer coordinates, clipped to the text rect. Both are in physical pixels.
+    fn fill_text_area(&mut self, rect: PixelRect, x: f32, y: f32, width: f32, height: f32, color: Color) {
+        let area = PixelRect {
+            left: rect.left + x.round() as i64,
+            top: rect.top + y.round() as i64,
+            right: rect.left + (x + width).round() as i64,
+            bottom: rect.top + (y + height).round() as i64,
+        };
+        self.fill_physical_rect(area.intersect(rect), color);
+    }
+
+    /// Measure the width of a single line of text, without any wrapping.
+    fn measure_text_width(
+        font_system: &mut cosmic_text::FontSystem,
+        metrics: cosmic_text::Metrics,
+        text: &str,
+        attrs: cosmic_text::Attrs,
+    ) -> f32 {
+        let mut buffer = cosmic_text::Buffer::new(font_system, metrics);
+        let mut buffer = buffer.borrow_with(font_system);
+        buffer.set_wrap(cosmic_text::Wrap::None);
+        buffer.set_text(text, attrs, cosmic_text::Shaping::Basic);
+        buffer.line_layout(0)
+            .and_then(|layout| layout.first())
+            .map(|line| line.w)
+            .unwrap_or(0.0)
+    }
+
+    /// Truncate every line of the buffer that does not fit in the given width, and replace the overflow with an ellipsis.
+    /// The ellipsis continues the span of the last kept character, so it is measured and drawn with its style.
+    /// The buffer is expected not to wrap, so each text line is a single layout line.
+    fn ellipsize(
+        buffer: &mut cosmic_text::Buffer,
+        font_system: &mut cosmic_text::FontSystem,
+        max_width: f32,
+        word_level: bool,
+    ) {
+        let metrics = buffer.metrics();
+        // width of the ellipsis by span, as spans can have another font or size
+        let mut ellipsis_widths: HashMap<usize, f32> = HashMap::new();
+        for line_index in 0..buffer.lines.len() {
+            // text index and right edge of each glyph, if the line does not fit
+            let glyphs: Vec<(usize, f32)> = match buffer.line_layout(font_system, line_index).and_then(|layout| layout.first()) {
+                Some(layout_line) if layout_line.w > max_width => layout_line.glyphs.iter().map(|glyph| (glyph.start, glyph.x + glyph.w)).collect(),
+                _ => continue, // the line fits, nothing to do
+            };
+            let attrs_list = buffer.lines[line_index].attrs_list().clone();
+            let mut ellipsis_width = |index: usize| {
+                let attrs = attrs_list.get_span(index);
+                *ellipsis_widths.entry(attrs.metadata).or_insert_with(|| Canvas::measure_text_width(font_system, metrics, ELLIPSIS, attrs))
+            };
+            // keep every glyph that fits with an ellipsis in its style behind it
+            let cut_index = glyphs.iter()
+                .find(|(start, end)| end + ellipsis_width(*start) > max_width)
+                .map(|(start, _)| *start);
+            if let Some(cut_index) = cut_index {
+                let line = &mut buffer.lines[line_index];
+                let (kept_text, cut_text) = line.text().split_at(cut_index);
+                let cut_on_word_boundary = cut_text.starts_with(char::is_whitespace);
+                let kept_text = match word_level && !cut_on_word_boundary {
+                    // at word level, drop the last word that got cut. If even the first word does not fit, only the ellipsis is left.
+                    true => kept_text.rfind(char::is_whitespace).map(|index| &kept_text[..index]).unwrap_or(""),
+                    false => kept_text,
+                }.trim_end();
+                let ellipsis_attrs = match kept_text.char_indices().last() {
+                    Some((index, _)) => attrs_list.get_span(index),
+                    None => attrs_list.get_span(cut_index),
+                };
+                // the spans are cut with the text, and the last one is extended over the ellipsis
+                let kept_length = kept_text.len();
+                let mut truncated_attrs = attrs_list.clone();
+                truncated_attrs.split_off(kept_length);
+                truncated_attrs.add_span(kept_length..kept_length + ELLIPSIS.len(), ellipsis_attrs);
+                let truncated = format!("{kept_text}{ELLIPSIS}");
+                let ending = line.ending();
+                line.set_text(truncated, ending, truncated_attrs);
+            }
+        }
+    }
+}
+
+/// Lay out the spans in a buffer of the given size. Without a width, lines are only broken on line feeds.
+/// Font sizes are multiplied by the scale factor, to lay out the text in physical pixels.
+fn layout_spans<'s>(
+    assets: &mut Assets,
+    spans: impl Iterator<Item = (&'s str, &'s SpanStyle)>,
+    style: &TextStyle,
+    width: Option<f32>,
+    height: Option<f32>,
+    scale_factor: f32,
+) -> cosmic_text::Buffer {
+    // Text metrics indicate the font size and line height of a buffer
+    // note to myself : line height is the space between the top of the buffer and the baseline of the text,
+    // then, it is also the space between the baseline of the text and the baseline of the next line.
+    let metrics = cosmic_text::Metrics::new(style.size * scale_factor, (style.size + style.additional_interline) * scale_factor);
+    // the family might not be available, in which case the registered fallbacks are used
+    let family = assets.text().resolve_family(&style.family);
+    let (font_system, _) = assets.text_mut().fonts_and_cache();
+
+    // all the style fields that are not layout related are carried by the attributes
+    let attrs: cosmic_text::Attrs = style.into();
+    let attrs = attrs.family((&family).into()).color(style.color.into());
+    let span_attrs = spans.enumerate().map(|(index, (text, span_style))| {
+        (text, span_style.apply(attrs, style, scale_factor).metadata(index))
+    });
+
+    // A Buffer provides shaping and layout for a UTF-8 string, create one per text widget
+    let mut text_buffer = cosmic_text::Buffer::new(font_system, metrics);
+    let mut buffer = text_buffer.borrow_with(font_system);
+
+    // Set a size for the text buffer, in pixels
+    buffer.set_size(width, height);
+    buffer.set_wrap(style.overflow.into());
+
+    // Add some text!
+    // basic shaping gives glyph indices relative to each word, and picks the span attributes with them,
+    // so the spans and the ellipsis cut would land on the wrong characters
+    buffer.set_rich_text(span_attrs, attrs, cosmic_text::Shaping::Advanced);
+
+    if let (true, Some(width)) = (style.overflow.is_ellipsis(), width) {
+        Canvas::ellipsize(&mut text_buffer, font_system, width, style.overflow.is_word_level());
+    }
+    let mut buffer = text_buffer.borrow_with(font_system);
+
+    for line in buffer.lines.iter_mut() {
+        line.set_align(Some(style.align));
+    }
+
+    // Perform shaping as desired
+    buffer.shape_until_scroll(false); // todo what is this for ? works fine without
+
+    text_buffer
+}
+
+/// Size of the spans laid out with the given max width: the width of the longest line, and the height of all lines.
+pub(crate) fn measure_spans<'s>(
+    assets: &mut Assets,
+    spans: impl Iterator<Item = (&'s str, &'s SpanStyle)>,
+    style: &TextStyle,
+    max_width: Option<f32>,
+) -> Size {
+    // layouts are computed in logical pixels
+    let buffer = layout_spans(assets, spans, style, max_width, None, 1.0);
+    buffer.layout_runs().fold(Size::ZERO, |size, run| Size::new(size.width.max(run.line_w), size.height + run.line_height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{layout_spans, ELLIPSIS};
+    use crate::{app::assets::Assets, widget::text_widgets::text::text_overflow::TextOverflow, SpanStyle, TextStyle};
+
+    #[test]
+    fn ellipsis_takes_the_style_of_the_span_it_cuts() {
+        let mut assets = Assets::new();
+        let large = SpanStyle { size: Some(40.), ..SpanStyle::new() };
+        let spans = [("Small ", &SpanStyle::new()), ("LARGE TEXT THAT OVERFLOWS", &large)];
+        let style = TextStyle::default().sized(12.).with_overflow(TextOverflow::CharEllipsis);
+        let buffer = layout_spans(&mut assets, spans.into_iter(), &style, Some(200.), None, 1.0);
+
+        let line = &buffer.lines[0];
+        assert!(line.text().starts_with("Small LA"), "{}", line.text());
+        let ellipsis_index = line.text().len() - ELLIPSIS.len();
+        assert_eq!(&line.text()[ellipsis_index..], ELLIPSIS);
+        assert_eq!(line.attrs_list().get_span(ellipsis_index).metadata, 1);
+        // the spans were cut with the text
+        assert!(line.attrs_list().spans().iter().all(|(range, _)| range.end <= line.text().len()));
+        // the ellipsis was measured with the large size, so the line still fits
+        let run = buffer.layout_runs().next().unwrap();
+        assert!(run.line_w <= 200., "line of {} pixels", run.line_w);
     }
-}
\ No newline at end of file
+}
@@ -1,9 +1,12 @@
 
 
 pub(crate) mod alignment;
+pub(crate) mod constraints;
 pub(crate) mod decoration;
+pub(crate) mod edge_insets;
+pub(crate) mod offset;
+pub(crate) mod point;
 pub(crate) mod rect;
 pub(crate) mod screen_side;
+pub(crate) mod size;
 pub(crate) mod size_requirements;
-
-
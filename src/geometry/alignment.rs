@@ -1,17 +1,40 @@
+use crate::{Rect, Size, WidgetSizeRequirement};
 
-
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HorizontalAlignment {
     Left,
     Center,
     Right,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VerticalAlignment {
     Top,
     Center,
     Bottom,
 }
 
+impl HorizontalAlignment {
+    pub fn get_left_space(&self, available_space: f32) -> f32 {
+        match self {
+            HorizontalAlignment::Left => 0.0,
+            HorizontalAlignment::Center => available_space / 2.0,
+            HorizontalAlignment::Right => available_space,
+        }
+    }
+}
+
+impl VerticalAlignment {
+    pub fn get_top_space(&self, available_space: f32) -> f32 {
+        match self {
+            VerticalAlignment::Top => 0.0,
+            VerticalAlignment::Center => available_space / 2.0,
+            VerticalAlignment::Bottom => available_space,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Alignment(HorizontalAlignment, VerticalAlignment);
 
 impl Alignment {
@@ -26,19 +49,54 @@ impl Alignment {
     pub const BOTTOM_RIGHT: Alignment = Alignment(HorizontalAlignment::Right, VerticalAlignment::Bottom);
 
 
-    pub fn get_left_space(&self, available_space: u32) -> u32 {
-        match self.0 {
-            HorizontalAlignment::Left => 0,
-            HorizontalAlignment::Center => available_space / 2,
-            HorizontalAlignment::Right => available_space,
+    pub fn get_left_space(&self, available_space: f32) -> f32 {
+        self.0.get_left_space(available_space)
+    }
+
+    pub fn get_top_space(&self, available_space: f32) -> f32 {
+        self.1.get_top_space(available_space)
+    }
+
+    /// Get the rect of a child with the given requirements, aligned in the given rect.
+    /// Children that can grow take all the space, others are given the size they want and aligned in the remaining space.
+    pub fn place_child(&self, child_requirements: (WidgetSizeRequirement, WidgetSizeRequirement), rect: Rect) -> Rect {
+        let (child_width, remaining_width) = Self::get_child_and_remaining_size(child_requirements.0, rect.width);
+        let (child_height, remaining_height) = Self::get_child_and_remaining_size(child_requirements.1, rect.height);
+        Rect {
+            x: rect.x + self.get_left_space(remaining_width),
+            y: rect.y + self.get_top_space(remaining_height),
+            width: child_width,
+            height: child_height,
         }
     }
 
-    pub fn get_top_space(&self, available_space: u32) -> u32 {
-        match self.1 {
-            VerticalAlignment::Top => 0,
-            VerticalAlignment::Center => available_space / 2,
-            VerticalAlignment::Bottom => available_space,
+    /// Get the rect of a child of the given size, aligned in the given rect.
+    /// Children bigger than the rect are reduced to the rect size.
+    pub fn place_size(&self, size: Size, rect: Rect) -> Rect {
+        let width = size.width.clamp(0.0, rect.width);
+        let height = size.height.clamp(0.0, rect.height);
+        Rect {
+            x: rect.x + self.get_left_space(rect.width - width),
+            y: rect.y + self.get_top_space(rect.height - height),
+            width,
+            height,
+        }
+    }
+
+    fn get_child_and_remaining_size(child_requirement: WidgetSizeRequirement, available_size: f32) -> (f32, f32) {
+        match child_requirement {
+            // child can have any size, so we give it all the available space.
+            WidgetSizeRequirement::None |
+            WidgetSizeRequirement::Flex { .. } |
+            WidgetSizeRequirement::Min { .. } => (available_size, 0.0),
+            // child does not want to be bigger than a given size,
+            // so we give it the minimum between the available space and the size it wants.
+            WidgetSizeRequirement::Fixed { size: max, .. } |
+            WidgetSizeRequirement::Max { max, .. } |
+            WidgetSizeRequirement::MinMax { max, .. } => (
+                max.min(available_size),
+                (available_size - max).max(0.0),
+            ),
         }
     }
 }
\ No newline at end of file
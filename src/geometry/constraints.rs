@@ -0,0 +1,117 @@
+use crate::{geometry::size_requirements::WidgetSizeRequirement, Rect, Size};
+
+/// Max value of a constraint that does not limit the size, for example along a scrolling axis.
+pub const UNBOUNDED: f32 = f32::INFINITY;
+
+/// Constraints given by a parent to its child during the layout pass.
+/// The child chooses its size within them, and returns it to the parent.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SizeConstraints {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+
+impl SizeConstraints {
+    /// The child must have exactly the given size.
+    pub const fn tight(width: f32, height: f32) -> SizeConstraints {
+        SizeConstraints {
+            min_width: width,
+            max_width: width,
+            min_height: height,
+            max_height: height,
+        }
+    }
+
+    /// The child must have exactly the size of the rect.
+    pub const fn tight_for(rect: Rect) -> SizeConstraints {
+        SizeConstraints::tight(rect.width, rect.height)
+    }
+
+    /// The child can have any size up to the given one.
+    pub const fn loose(width: f32, height: f32) -> SizeConstraints {
+        SizeConstraints {
+            min_width: 0.0,
+            max_width: width,
+            min_height: 0.0,
+            max_height: height,
+        }
+    }
+
+    /// Same constraints, with no minimum size.
+    pub const fn loosen(self) -> SizeConstraints {
+        SizeConstraints {
+            min_width: 0.0,
+            min_height: 0.0,
+            ..self
+        }
+    }
+
+    /// Same constraints, with no limit on the width.
+    pub const fn with_unbounded_width(self) -> SizeConstraints {
+        SizeConstraints {
+            max_width: UNBOUNDED,
+            ..self
+        }
+    }
+
+    /// Same constraints, with no limit on the height.
+    pub const fn with_unbounded_height(self) -> SizeConstraints {
+        SizeConstraints {
+            max_height: UNBOUNDED,
+            ..self
+        }
+    }
+
+    /// Constraints reduced by the given amounts, for example to leave space for a padding.
+    pub fn shrink(self, horizontal: f32, vertical: f32) -> SizeConstraints {
+        // unbounded values stay unbounded, as infinity minus anything is still infinity
+        let shrink = |value: f32, amount: f32| (value - amount).max(0.0);
+        SizeConstraints {
+            min_width: shrink(self.min_width, horizontal),
+            max_width: shrink(self.max_width, horizontal),
+            min_height: shrink(self.min_height, vertical),
+            max_height: shrink(self.max_height, vertical),
+        }
+    }
+
+    pub const fn is_width_bounded(&self) -> bool {
+        self.max_width.is_finite()
+    }
+
+    pub const fn is_height_bounded(&self) -> bool {
+        self.max_height.is_finite()
+    }
+
+    /// Closest size to the given one that respects the constraints.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size {
+            width: size.width.clamp(self.min_width, self.max_width.max(self.min_width)),
+            height: size.height.clamp(self.min_height, self.max_height.max(self.min_height)),
+        }
+    }
+
+    /// Size that fills the bounded width, and takes the measured height of the content unless the height is tight.
+    /// The content is measured with the width it will be given, so wrapped lines get the height they need.
+    /// The content is only measured if the height is not tight or the width is unbounded.
+    pub fn fill_or_measure(&self, measure: impl FnOnce() -> Size) -> Size {
+        let height_tight = self.min_height >= self.max_height;
+        if self.is_width_bounded() && height_tight {
+            return self.constrain(Size::new(self.max_width, self.max_height));
+        }
+        let measured = measure();
+        self.constrain(Size {
+            width: if self.is_width_bounded() { self.max_width } else { measured.width },
+            height: if height_tight { self.max_height } else { measured.height },
+        })
+    }
+
+    /// Size taken by a widget with the given requirements within the constraints.
+    pub fn fit(&self, (width, height): (WidgetSizeRequirement, WidgetSizeRequirement)) -> Size {
+        Size {
+            width: width.resolve(self.min_width, self.max_width),
+            height: height.resolve(self.min_height, self.max_height),
+        }
+    }
+}
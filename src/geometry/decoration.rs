@@ -1,5 +1,7 @@
 use tiny_skia::PathBuilder;
 
+use crate::Rect;
+
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct BorderType(u8);
@@ -21,89 +23,47 @@ impl BorderType {
 
 #[cfg(feature = "skia")]
 impl BorderType {
-    pub fn build_path(&self, path: &mut PathBuilder, rect: softbuffer::Rect, border_width: &mut u32, radius: u32) {
+    pub fn build_path(&self, path: &mut PathBuilder, rect: Rect, border_width: &mut f32, radius: f32) {
 
+        let half_size = (rect.width / 2.0).min(rect.height / 2.0);
         // radius can not be smaller than the border width, nor bigger than half the rect size
-        let radius = radius.max(*border_width).min((rect.width.get() >> 1).min(rect.height.get() >> 1));
+        let radius = radius.max(*border_width).min(half_size);
         // the border width can not be bigger than half the rect size
-        *border_width = (*border_width).min(rect.width.get() >> 1).min(rect.height.get() >> 1);
+        *border_width = border_width.min(half_size);
 
-        let border_offset = *border_width >> 1;
+        let border_offset = *border_width / 2.0;
+        let (left, top, right, bottom) = (rect.x, rect.y, rect.right(), rect.bottom());
         // top left corner
         if self.0 & 1 > 0 {
-            path.move_to(
-                (rect.x + border_offset) as f32,
-                (rect.y + radius) as f32
-            );
-            path.quad_to(
-                (rect.x + border_offset) as f32,
-                (rect.y + border_offset) as f32,
-                (rect.x + radius) as f32,
-                (rect.y + border_offset) as f32,
-            );
+            path.move_to(left + border_offset, top + radius);
+            path.quad_to(left + border_offset, top + border_offset, left + radius, top + border_offset);
         }
         else {
-            path.move_to(
-                (rect.x + border_offset) as f32,
-                (rect.y + border_offset) as f32
-            );
+            path.move_to(left + border_offset, top + border_offset);
         }
         // top right corner
         if self.0 & (1 << 1) > 0 {
-            path.line_to(
-                (rect.x + rect.width.get() - radius) as f32,
-                (rect.y + border_offset) as f32
-            );
-            path.quad_to(
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + border_offset) as f32,
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + radius) as f32,
-            );
+            path.line_to(right - radius, top + border_offset);
+            path.quad_to(right - border_offset, top + border_offset, right - border_offset, top + radius);
         }
         else {
-            path.line_to(
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + border_offset) as f32
-            );
+            path.line_to(right - border_offset, top + border_offset);
         }
         // bottom right corner
         if self.0 & (1 << 2) > 0 {
-            path.line_to(
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + rect.height.get()) as f32 - radius as f32,
-            );
-            path.quad_to(
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + rect.height.get() - border_offset) as f32,
-                (rect.x + rect.width.get()) as f32 - radius as f32,
-                (rect.y + rect.height.get() - border_offset) as f32,
-            );
+            path.line_to(right - border_offset, bottom - radius);
+            path.quad_to(right - border_offset, bottom - border_offset, right - radius, bottom - border_offset);
         }
         else {
-            path.line_to(
-                (rect.x + rect.width.get() - border_offset) as f32,
-                (rect.y + rect.height.get() - border_offset) as f32
-            );
+            path.line_to(right - border_offset, bottom - border_offset);
         }
         // bottom left corner
         if self.0 & (1 << 3) > 0 {
-            path.line_to(
-                (rect.x + radius) as f32,
-                (rect.y + rect.height.get() - border_offset) as f32,
-            );
-            path.quad_to(
-                (rect.x + border_offset) as f32,
-                (rect.y + rect.height.get() - border_offset) as f32,
-                (rect.x + border_offset) as f32,
-                (rect.y + rect.height.get()) as f32 - radius as f32,
-            );
+            path.line_to(left + radius, bottom - border_offset);
+            path.quad_to(left + border_offset, bottom - border_offset, left + border_offset, bottom - radius);
         }
         else {
-            path.line_to(
-                (rect.x + border_offset) as f32,
-                (rect.y + rect.height.get() - border_offset) as f32
-            );
+            path.line_to(left + border_offset, bottom - border_offset);
         }
         // close the path
         path.close();
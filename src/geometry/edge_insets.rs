@@ -0,0 +1,111 @@
+use crate::{PaddType, Rect, WidgetSizeRequirement};
+
+/// Amounts of space on each side of a rect, in logical pixels.
+/// This is used for the padding of the `Padder`, and for the margins of the containers that have them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct EdgeInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl EdgeInsets {
+    pub const ZERO: EdgeInsets = EdgeInsets { top: 0.0, right: 0.0, bottom: 0.0, left: 0.0 };
+
+    pub fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        EdgeInsets { top, right, bottom, left }
+    }
+
+    /// The same amount on all sides.
+    pub fn all(amount: f32) -> Self {
+        EdgeInsets { top: amount, right: amount, bottom: amount, left: amount }
+    }
+
+    /// The vertical amount on the top and bottom sides, and the horizontal amount on the left and right sides.
+    pub fn symmetric(vertical: f32, horizontal: f32) -> Self {
+        EdgeInsets { top: vertical, right: horizontal, bottom: vertical, left: horizontal }
+    }
+
+    /// The amount on the given sides only, the other sides are zero.
+    pub fn only(sides: PaddType, amount: f32) -> Self {
+        let side = |side: PaddType| match sides & side == side {
+            true => amount,
+            false => 0.0,
+        };
+        EdgeInsets {
+            top: side(PaddType::TOP),
+            right: side(PaddType::RIGHT),
+            bottom: side(PaddType::BOTTOM),
+            left: side(PaddType::LEFT),
+        }
+    }
+
+    /// Total amount on the left and right sides.
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    /// Total amount on the top and bottom sides.
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+
+    /// The rect left inside the insets. It is empty if the insets take all the space.
+    pub fn deflate(&self, rect: Rect) -> Rect {
+        Rect {
+            x: rect.x + self.left,
+            y: rect.y + self.top,
+            width: (rect.width - self.horizontal()).max(0.0),
+            height: (rect.height - self.vertical()).max(0.0),
+        }
+    }
+
+    /// Requirements of a widget with the given requirements, surrounded by the insets.
+    pub fn inflate_requirements(&self, (width, height): (WidgetSizeRequirement, WidgetSizeRequirement)) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        (width + self.horizontal(), height + self.vertical())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::EdgeInsets;
+    use crate::{PaddType, Rect, WidgetSizeRequirement};
+
+    #[test]
+    fn only_pads_the_given_sides() {
+        assert_eq!(EdgeInsets::only(PaddType::TOP | PaddType::LEFT, 5.), EdgeInsets::new(5., 0., 0., 5.));
+        assert_eq!(EdgeInsets::only(PaddType::HORIZONTAL, 3.), EdgeInsets::symmetric(0., 3.));
+        assert_eq!(EdgeInsets::only(PaddType::ALL, 2.), EdgeInsets::all(2.));
+        assert_eq!(EdgeInsets::only(PaddType::NONE, 2.), EdgeInsets::ZERO);
+    }
+
+    #[test]
+    fn deflate_removes_the_insets() {
+        let insets = EdgeInsets::new(1., 2., 3., 4.);
+        assert_eq!(insets.deflate(Rect::new(10., 10., 100., 50.)), Rect::new(14., 11., 94., 46.));
+    }
+
+    #[test]
+    fn deflate_clamps_to_an_empty_rect() {
+        let insets = EdgeInsets::all(10.);
+        assert_eq!(insets.deflate(Rect::new(0., 0., 15., 30.)), Rect::new(10., 10., 0., 10.));
+        assert_eq!(insets.deflate(Rect::new(0., 0., 5., 5.)), Rect::new(10., 10., 0., 0.));
+    }
+
+    #[test]
+    fn inflate_requirements_adds_the_insets() {
+        let insets = EdgeInsets::new(1., 2., 3., 4.);
+        let flex = NonZeroU32::MIN;
+        assert_eq!(
+            insets.inflate_requirements((WidgetSizeRequirement::Fixed { size: 10. }, WidgetSizeRequirement::Flex { flex })),
+            (WidgetSizeRequirement::Fixed { size: 16. }, WidgetSizeRequirement::Min { min: 4., flex }),
+        );
+        assert_eq!(
+            EdgeInsets::ZERO.inflate_requirements((WidgetSizeRequirement::Flex { flex }, WidgetSizeRequirement::Max { max: 5., flex })),
+            (WidgetSizeRequirement::Flex { flex }, WidgetSizeRequirement::Max { max: 5., flex }),
+        );
+    }
+}
@@ -0,0 +1,35 @@
+/// A displacement, in logical pixels. This is the difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Offset {
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Offset {
+    pub const ZERO: Offset = Offset { dx: 0.0, dy: 0.0 };
+
+    pub const fn new(dx: f32, dy: f32) -> Self {
+        Offset { dx, dy }
+    }
+}
+
+impl std::ops::Add for Offset {
+    type Output = Offset;
+    fn add(self, rhs: Self) -> Self::Output {
+        Offset { dx: self.dx + rhs.dx, dy: self.dy + rhs.dy }
+    }
+}
+
+impl std::ops::Sub for Offset {
+    type Output = Offset;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Offset { dx: self.dx - rhs.dx, dy: self.dy - rhs.dy }
+    }
+}
+
+impl std::ops::Neg for Offset {
+    type Output = Offset;
+    fn neg(self) -> Self::Output {
+        Offset { dx: -self.dx, dy: -self.dy }
+    }
+}
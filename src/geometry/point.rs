@@ -0,0 +1,44 @@
+use crate::geometry::offset::Offset;
+
+/// A position, in logical pixels.
+/// Positions are not limited to the window: content scrolled out of view has negative coordinates.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl Point {
+    pub const ZERO: Point = Point { x: 0.0, y: 0.0 };
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Point { x, y }
+    }
+
+    /// The offset from the origin to this point.
+    pub const fn to_offset(self) -> Offset {
+        Offset { dx: self.x, dy: self.y }
+    }
+}
+
+impl std::ops::Add<Offset> for Point {
+    type Output = Point;
+    fn add(self, rhs: Offset) -> Self::Output {
+        Point { x: self.x + rhs.dx, y: self.y + rhs.dy }
+    }
+}
+
+impl std::ops::Sub<Offset> for Point {
+    type Output = Point;
+    fn sub(self, rhs: Offset) -> Self::Output {
+        Point { x: self.x - rhs.dx, y: self.y - rhs.dy }
+    }
+}
+
+/// The offset to go from rhs to self.
+impl std::ops::Sub for Point {
+    type Output = Offset;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Offset { dx: self.x - rhs.x, dy: self.y - rhs.y }
+    }
+}
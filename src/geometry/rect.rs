@@ -1,28 +1,62 @@
-use std::num::NonZeroU32;
+use crate::geometry::{offset::Offset, point::Point, size::Size};
+
+/// An axis aligned rect, in logical pixels.
+///
+/// Rects can be empty, and can be placed anywhere: children of a scrolled view can start above or left of the window.
+/// Positions and sizes are not rounded, the canvas snaps them to the physical pixels when drawing.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
 
+impl Rect {
+    pub const ZERO: Rect = Rect { x: 0.0, y: 0.0, width: 0.0, height: 0.0 };
 
-pub type Rect = softbuffer::Rect;
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Rect { x, y, width, height }
+    }
 
-pub struct NullableRect {
-    pub x: u32,
-    pub y: u32,
-    pub width: u32,
-    pub height: u32,
-}
+    pub const fn from_origin_size(origin: Point, size: Size) -> Self {
+        Rect { x: origin.x, y: origin.y, width: size.width, height: size.height }
+    }
 
+    /// Rect of the given size, with its top left corner at the origin.
+    pub const fn from_size(size: Size) -> Self {
+        Rect::from_origin_size(Point::ZERO, size)
+    }
 
+    /// Top left corner.
+    pub const fn origin(&self) -> Point {
+        Point { x: self.x, y: self.y }
+    }
 
+    pub const fn size(&self) -> Size {
+        Size { width: self.width, height: self.height }
+    }
 
-impl TryFrom<NullableRect> for softbuffer::Rect {
-    type Error = ();
-    fn try_from(value: NullableRect) -> Result<Self, Self::Error> {
-        match (NonZeroU32::new(value.width), NonZeroU32::new(value.height)) {
-            (Some(width), Some(height)) => Ok(softbuffer::Rect {
-                x: value.x, y: value.y, width, height
-            }),
-            _ => Err(())
-        }
+    pub fn right(&self) -> f32 {
+        self.x + self.width
     }
-}
 
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// Whether the rect covers no area.
+    pub fn is_empty(&self) -> bool {
+        self.size().is_empty()
+    }
 
+    /// Whether the point is in the rect. The left and top edges are in the rect, the right and bottom ones are not.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x >= self.x && point.x < self.right() && point.y >= self.y && point.y < self.bottom()
+    }
+
+    /// Same rect, moved by the offset.
+    pub fn translate(&self, offset: Offset) -> Rect {
+        Rect::from_origin_size(self.origin() + offset, self.size())
+    }
+}
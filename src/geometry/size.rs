@@ -0,0 +1,20 @@
+/// A width and a height, in logical pixels.
+/// Sizes can be zero, for widgets that take no space.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Size {
+    pub const ZERO: Size = Size { width: 0.0, height: 0.0 };
+
+    pub const fn new(width: f32, height: f32) -> Self {
+        Size { width, height }
+    }
+
+    /// Whether the size covers no area.
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+}
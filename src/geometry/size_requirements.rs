@@ -2,33 +2,33 @@ use std::num::NonZeroU32;
 
 
 
-/// Size requirements a widget can have.
+/// Size requirements a widget can have, in logical pixels.
 /// Here, widgets have no minimum sizes, as the display size can be as small as possible.
 /// Widgets can require to have a fix size, or a maximum size.
-#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum WidgetSizeRequirement {
     /// The widget request a fixed size.
     Fixed {
-        size: NonZeroU32,
+        size: f32,
     },
     /// The widget request a minimum size, but can be expanded.
     /// This requirement is a soft requirement, and can be ignored.
     /// However, in debug mode, size overflow will be checked.
     Min {
-        min: NonZeroU32,
+        min: f32,
         flex: NonZeroU32,
     },
     /// The widget request a maximum size, but can be reduced.
     Max {
-        max: NonZeroU32,
+        max: f32,
         flex: NonZeroU32,
     },
     /// The widget request a minimum and a maximum size.
     /// The min requirement is a soft requirement, and can be ignored.
     /// However, in debug mode, size overflow will be checked.
     MinMax {
-        min: NonZeroU32,
-        max: NonZeroU32,
+        min: f32,
+        max: f32,
         flex: NonZeroU32,
     },
     /// The widget have no size constraints, and will fill up all the available space.
@@ -68,11 +68,11 @@ impl std::ops::BitOr for WidgetSizeRequirement {
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Flex { flex }) =>
                 WidgetSizeRequirement::Min { min: size, flex },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Min { min, flex }) =>
-                WidgetSizeRequirement::Min { min: size.saturating_add(min.get()), flex },
+                WidgetSizeRequirement::Min { min: size.max(min), flex },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Max { max, flex }) =>
-                WidgetSizeRequirement::MinMax { min: size, max: max.saturating_add(size.get()), flex, },
+                WidgetSizeRequirement::MinMax { min: size, max: max.max(size), flex, },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::MinMax { min, max, flex }) =>
-                WidgetSizeRequirement::MinMax { min: min.saturating_add(size.get()), max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::MinMax { min: min.max(size), max: max.max(size), flex },
             // Flex, other
             (WidgetSizeRequirement::Flex { flex }, WidgetSizeRequirement::Fixed { size }) =>
                 WidgetSizeRequirement::Min { min: size, flex },
@@ -86,37 +86,37 @@ impl std::ops::BitOr for WidgetSizeRequirement {
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get())},
             // Min, other
             (WidgetSizeRequirement::Min { min, flex }, WidgetSizeRequirement::Fixed { size }) => 
-                WidgetSizeRequirement::Min { min: min.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::Min { min: min.max(size), flex },
             (WidgetSizeRequirement::Min { min, flex: f1 }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min: m1, flex: f1 }, WidgetSizeRequirement::Min { min: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min, flex: f1 }, WidgetSizeRequirement::Max { flex: f2, .. }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min: m1, flex: f1 }, WidgetSizeRequirement::MinMax { min: m2, flex: f2, .. }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             // Max, other
             (WidgetSizeRequirement::Max { max, flex }, WidgetSizeRequirement::Fixed { size }) =>
-                WidgetSizeRequirement::Max { max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::MinMax { min: size, max: max.max(size), flex },
             (WidgetSizeRequirement::Max { flex: f1, .. }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Flex { flex: f1.saturating_add(f2.get())},
             (WidgetSizeRequirement::Max { flex: f1, .. }, WidgetSizeRequirement::Min { min, flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Max { max: m1, flex: f1 }, WidgetSizeRequirement::Max { max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Max { max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Max { max: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Max { max: m1, flex: f1 }, WidgetSizeRequirement::MinMax { min, max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::MinMax { min, max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::MinMax { min, max: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             // MinMax, other
             (WidgetSizeRequirement::MinMax { min, max, flex }, WidgetSizeRequirement::Fixed { size }) =>
-                WidgetSizeRequirement::MinMax { min: min.saturating_add(size.get()), max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::MinMax { min: min.max(size), max: max.max(size), flex },
             (WidgetSizeRequirement::MinMax { min, flex: f1, .. }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min: m1, flex: f1, .. }, WidgetSizeRequirement::Min { min: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min, max: m1, flex: f1 }, WidgetSizeRequirement::Max { max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::MinMax { min, max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::MinMax { min, max: m1.max(m2), flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min: mi1, max: ma1, flex: f1 }, WidgetSizeRequirement::MinMax { min: mi2, max: ma2, flex: f2 }) =>
-            WidgetSizeRequirement::MinMax { min: mi1.saturating_add(mi2.get()), max: ma1.saturating_add(ma2.get()), flex: f1.saturating_add(f2.get()) },
+            WidgetSizeRequirement::MinMax { min: mi1.max(mi2), max: ma1.max(ma2), flex: f1.saturating_add(f2.get()) },
         }
     }
 }
@@ -140,15 +140,15 @@ impl std::ops::BitAnd for WidgetSizeRequirement {
             (WidgetSizeRequirement::None, other) => other,
             // Fixed, other
             (WidgetSizeRequirement::Fixed { size: s1 }, WidgetSizeRequirement::Fixed { size: s2 }) =>
-                WidgetSizeRequirement::Fixed { size: s1.saturating_add(s2.get()) },
+                WidgetSizeRequirement::Fixed { size: s1 + s2 },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Flex { flex }) =>
                 WidgetSizeRequirement::Min { min: size, flex },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Min { min, flex }) =>
-                WidgetSizeRequirement::Min { min: size.saturating_add(min.get()), flex },
+                WidgetSizeRequirement::Min { min: size + min, flex },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::Max { max, flex }) =>
-                WidgetSizeRequirement::MinMax { min: size, max: max.saturating_add(size.get()), flex, },
+                WidgetSizeRequirement::MinMax { min: size, max: max + size, flex, },
             (WidgetSizeRequirement::Fixed { size }, WidgetSizeRequirement::MinMax { min, max, flex }) =>
-                WidgetSizeRequirement::MinMax { min: min.saturating_add(size.get()), max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::MinMax { min: min + size, max: max + size, flex },
             // Flex, other
             (WidgetSizeRequirement::Flex { flex }, WidgetSizeRequirement::Fixed { size }) =>
                 WidgetSizeRequirement::Min { min: size, flex },
@@ -162,61 +162,61 @@ impl std::ops::BitAnd for WidgetSizeRequirement {
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get())},
             // Min, other
             (WidgetSizeRequirement::Min { min, flex }, WidgetSizeRequirement::Fixed { size }) => 
-                WidgetSizeRequirement::Min { min: min.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::Min { min: min + size, flex },
             (WidgetSizeRequirement::Min { min, flex: f1 }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min: m1, flex: f1 }, WidgetSizeRequirement::Min { min: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1 + m2, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min, flex: f1 }, WidgetSizeRequirement::Max { flex: f2, .. }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Min { min: m1, flex: f1 }, WidgetSizeRequirement::MinMax { min: m2, flex: f2, .. }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1 + m2, flex: f1.saturating_add(f2.get()) },
             // Max, other
             (WidgetSizeRequirement::Max { max, flex }, WidgetSizeRequirement::Fixed { size }) =>
-                WidgetSizeRequirement::Max { max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::Max { max: max + size, flex },
             (WidgetSizeRequirement::Max { flex: f1, .. }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Flex { flex: f1.saturating_add(f2.get())},
             (WidgetSizeRequirement::Max { flex: f1, .. }, WidgetSizeRequirement::Min { min, flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Max { max: m1, flex: f1 }, WidgetSizeRequirement::Max { max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Max { max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Max { max: m1 + m2, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::Max { max: m1, flex: f1 }, WidgetSizeRequirement::MinMax { min, max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::MinMax { min, max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::MinMax { min, max: m1 + m2, flex: f1.saturating_add(f2.get()) },
             // MinMax, other
             (WidgetSizeRequirement::MinMax { min, max, flex }, WidgetSizeRequirement::Fixed { size }) =>
-                WidgetSizeRequirement::MinMax { min: min.saturating_add(size.get()), max: max.saturating_add(size.get()), flex },
+                WidgetSizeRequirement::MinMax { min: min + size, max: max + size, flex },
             (WidgetSizeRequirement::MinMax { min, flex: f1, .. }, WidgetSizeRequirement::Flex { flex: f2 }) =>
                 WidgetSizeRequirement::Min { min, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min: m1, flex: f1, .. }, WidgetSizeRequirement::Min { min: m2, flex: f2 }) =>
-                WidgetSizeRequirement::Min { min: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::Min { min: m1 + m2, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min, max: m1, flex: f1 }, WidgetSizeRequirement::Max { max: m2, flex: f2 }) =>
-                WidgetSizeRequirement::MinMax { min, max: m1.saturating_add(m2.get()), flex: f1.saturating_add(f2.get()) },
+                WidgetSizeRequirement::MinMax { min, max: m1 + m2, flex: f1.saturating_add(f2.get()) },
             (WidgetSizeRequirement::MinMax { min: mi1, max: ma1, flex: f1 }, WidgetSizeRequirement::MinMax { min: mi2, max: ma2, flex: f2 }) =>
-            WidgetSizeRequirement::MinMax { min: mi1.saturating_add(mi2.get()), max: ma1.saturating_add(ma2.get()), flex: f1.saturating_add(f2.get()) },
+            WidgetSizeRequirement::MinMax { min: mi1 + mi2, max: ma1 + ma2, flex: f1.saturating_add(f2.get()) },
         }
     }
 }
 
-impl core::ops::Add<u32> for WidgetSizeRequirement {
+impl core::ops::Add<f32> for WidgetSizeRequirement {
     type Output = Self;
-    fn add(self, rhs: u32) -> Self::Output {
+    fn add(self, rhs: f32) -> Self::Output {
         match self {
-            WidgetSizeRequirement::Fixed { size } => WidgetSizeRequirement::Fixed { size: size.saturating_add(rhs) },
-            WidgetSizeRequirement::Flex { flex } => match NonZeroU32::new(rhs) {
-                Some(min) => WidgetSizeRequirement::Min { min, flex },
-                None => WidgetSizeRequirement::Flex { flex },
+            WidgetSizeRequirement::Fixed { size } => WidgetSizeRequirement::Fixed { size: size + rhs },
+            WidgetSizeRequirement::Flex { flex } => match rhs > 0.0 {
+                true => WidgetSizeRequirement::Min { min: rhs, flex },
+                false => WidgetSizeRequirement::Flex { flex },
             },
-            WidgetSizeRequirement::Max { max, flex } => match NonZeroU32::new(rhs) {
-                Some(min) => WidgetSizeRequirement::MinMax { min, max: max.saturating_add(rhs), flex, },
-                None => WidgetSizeRequirement::Max { max, flex },
+            WidgetSizeRequirement::Max { max, flex } => match rhs > 0.0 {
+                true => WidgetSizeRequirement::MinMax { min: rhs, max: max + rhs, flex, },
+                false => WidgetSizeRequirement::Max { max, flex },
             },
             WidgetSizeRequirement::Min { min, flex } =>
-                WidgetSizeRequirement::Min { min: min.saturating_add(rhs), flex },
+                WidgetSizeRequirement::Min { min: min + rhs, flex },
             WidgetSizeRequirement::MinMax { min, max, flex } =>
-                WidgetSizeRequirement::MinMax { min: min.saturating_add(rhs), max: max.saturating_add(rhs), flex },
-            WidgetSizeRequirement::None => match NonZeroU32::new(rhs) {
-                Some(min) => WidgetSizeRequirement::Fixed { size: min },
-                None => WidgetSizeRequirement::None,
+                WidgetSizeRequirement::MinMax { min: min + rhs, max: max + rhs, flex },
+            WidgetSizeRequirement::None => match rhs > 0.0 {
+                true => WidgetSizeRequirement::Fixed { size: rhs },
+                false => WidgetSizeRequirement::None,
             },
         }
     }
@@ -225,153 +225,325 @@ impl core::ops::Add<u32> for WidgetSizeRequirement {
 impl core::ops::Mul<u32> for WidgetSizeRequirement {
     type Output = Self;
     fn mul(self, rhs: u32) -> Self::Output {
+        let scale = rhs as f32;
         match (NonZeroU32::new(rhs), self) {
-            (Some(rhs), WidgetSizeRequirement::Fixed { size }) => WidgetSizeRequirement::Fixed { size: size.saturating_mul(rhs) },
+            (Some(_), WidgetSizeRequirement::Fixed { size }) => WidgetSizeRequirement::Fixed { size: size * scale },
             (Some(rhs), WidgetSizeRequirement::Flex { flex }) => WidgetSizeRequirement::Flex { flex: flex.saturating_mul(rhs) },
-            (Some(rhs), WidgetSizeRequirement::Max { max, flex }) => WidgetSizeRequirement::Max { max: max.saturating_mul(rhs), flex: flex.saturating_mul(rhs) },
-            (Some(rhs), WidgetSizeRequirement::Min { min, flex }) => WidgetSizeRequirement::Min { min: min.saturating_mul(rhs), flex: flex.saturating_mul(rhs) },
-            (Some(rhs), WidgetSizeRequirement::MinMax { min, max, flex }) => WidgetSizeRequirement::MinMax { min: min.saturating_mul(rhs), max: max.saturating_mul(rhs), flex: flex.saturating_mul(rhs) },
+            (Some(rhs), WidgetSizeRequirement::Max { max, flex }) => WidgetSizeRequirement::Max { max: max * scale, flex: flex.saturating_mul(rhs) },
+            (Some(rhs), WidgetSizeRequirement::Min { min, flex }) => WidgetSizeRequirement::Min { min: min * scale, flex: flex.saturating_mul(rhs) },
+            (Some(rhs), WidgetSizeRequirement::MinMax { min, max, flex }) => WidgetSizeRequirement::MinMax { min: min * scale, max: max * scale, flex: flex.saturating_mul(rhs) },
             (None, _) | (Some(_), WidgetSizeRequirement::None) => WidgetSizeRequirement::None,
         }
     }
 }
 
 impl WidgetSizeRequirement {
-    /// Distribute a given available_space between multiple requirements.
-    /// The algorithm will do it's best to respect all provided requirements, but it is sometimes impossible.
-    /// todo: rework this whole thing, as it is super primitive for now.
-    /// I'm convinced there is a nice mathy elegant way to do it, and I've started searching it.
-    pub fn distribute_available_size<const N: usize>(requirements: [WidgetSizeRequirement; N], available_space: NonZeroU32) -> [u32; N] {
-
-        let mut results = [0; N];
+    /// Size of a widget that is not limited by the available space, such as the content of a scroll view.
+    /// The widget gets at least the visible space, and more if it requires it.
+    pub fn unbounded_size(&self, visible_space: f32) -> f32 {
+        match self {
+            WidgetSizeRequirement::Fixed { size } => size.max(visible_space),
+            WidgetSizeRequirement::Min { min, .. } => min.max(visible_space),
+            WidgetSizeRequirement::MinMax { min, .. } => min.max(visible_space),
+            WidgetSizeRequirement::Max { .. } => visible_space,
+            WidgetSizeRequirement::Flex { .. } => visible_space,
+            WidgetSizeRequirement::None => visible_space,
+        }
+    }
 
-        // Step 1: get the number of min space required and flex components
+    /// Limit the requirement to the given maximum size.
+    /// Widgets that could grow without limit get this maximum, and any size above it is reduced to it.
+    pub fn at_most(self, limit: f32) -> WidgetSizeRequirement {
+        match self {
+            WidgetSizeRequirement::Fixed { size } => WidgetSizeRequirement::Fixed { size: size.min(limit) },
+            WidgetSizeRequirement::Flex { flex } => WidgetSizeRequirement::Max { max: limit, flex },
+            WidgetSizeRequirement::Min { min, flex } => match min >= limit {
+                true => WidgetSizeRequirement::Fixed { size: limit },
+                false => WidgetSizeRequirement::MinMax { min, max: limit, flex },
+            },
+            WidgetSizeRequirement::Max { max, flex } => WidgetSizeRequirement::Max { max: max.min(limit), flex },
+            WidgetSizeRequirement::MinMax { min, max, flex } => match min >= limit {
+                true => WidgetSizeRequirement::Fixed { size: limit },
+                false => WidgetSizeRequirement::MinMax { min, max: max.min(limit), flex },
+            },
+            WidgetSizeRequirement::None => WidgetSizeRequirement::None,
+        }
+    }
 
-        let (min_requirements, total_flex): (u32, u32) = requirements.iter().map(|req| match req {
-            WidgetSizeRequirement::Fixed { size } => (size.get(), 0),
-            WidgetSizeRequirement::Flex { flex } => (0, flex.get()),
-            WidgetSizeRequirement::Max { flex, .. } => (0, flex.get(), ),
-            WidgetSizeRequirement::Min { min, flex } => (min.get(), flex.get()),
-            WidgetSizeRequirement::MinMax { min, flex, .. } => (min.get(), flex.get(), ),
-            WidgetSizeRequirement::None => (0, 0),
-        }).fold((0, 0), |(a1, b1), (a2, b2)| (a1 + a2, b1 + b2));
+    /// Size a widget takes on its own, when it can be anything between min and max.
+    /// Widgets that can grow take the max size when it is bounded, and their smallest size otherwise.
+    /// The result is always between min and max.
+    pub fn resolve(&self, min: f32, max: f32) -> f32 {
+        let bounded = max.is_finite();
+        let size = match *self {
+            WidgetSizeRequirement::Fixed { size } => size,
+            WidgetSizeRequirement::Min { min: required_min, .. } => match bounded {
+                true => required_min.max(max),
+                false => required_min,
+            },
+            WidgetSizeRequirement::Max { max: required_max, .. } => required_max.min(max),
+            WidgetSizeRequirement::MinMax { min: required_min, max: required_max, .. } => match bounded {
+                true => max.clamp(required_min, required_max.max(required_min)),
+                false => required_max,
+            },
+            WidgetSizeRequirement::Flex { .. } => match bounded {
+                true => max,
+                false => min,
+            },
+            WidgetSizeRequirement::None => 0.0,
+        };
+        size.clamp(min, max.max(min))
+    }
 
-        if min_requirements > available_space.get() {
-            panic!("TODO: handle case where not enough space for min requirements! ({min_requirements} required, got {available_space})");            
+    /// Size a widget would like to have when it can be given any size, if it has one.
+    /// Widgets that only want to fill the available space have no preferred size.
+    pub fn preferred_size(&self) -> Option<f32> {
+        match self {
+            WidgetSizeRequirement::Fixed { size } => Some(*size),
+            WidgetSizeRequirement::Min { min, .. } => Some(*min),
+            WidgetSizeRequirement::MinMax { min, .. } => Some(*min),
+            WidgetSizeRequirement::Max { max, .. } => Some(*max),
+            WidgetSizeRequirement::Flex { .. } => None,
+            WidgetSizeRequirement::None => None,
         }
+    }
 
-        // Step 2: distribute min requirements
+    /// Smallest size the widget requires.
+    fn min_size(&self) -> f32 {
+        match self {
+            WidgetSizeRequirement::Fixed { size: min } |
+            WidgetSizeRequirement::Min { min, .. } |
+            WidgetSizeRequirement::MinMax { min, .. } => min.max(0.0),
+            _ => 0.0,
+        }
+    }
 
-        for (result, requirement) in results.iter_mut().zip(requirements.iter()) {
-            match requirement {
-                WidgetSizeRequirement::Fixed { size: min } |
-                WidgetSizeRequirement::Min { min, .. } |
-                WidgetSizeRequirement::MinMax { min, .. } => *result = min.get(),
-                _ => {},
-            }
+    /// Flex value and maximum size of a widget that can grow, None for widgets that can't.
+    fn growth(&self) -> Option<(NonZeroU32, f32)> {
+        match *self {
+            WidgetSizeRequirement::Flex { flex } |
+            WidgetSizeRequirement::Min { flex, .. } => Some((flex, f32::INFINITY)),
+            WidgetSizeRequirement::Max { max, flex } |
+            WidgetSizeRequirement::MinMax { max, flex, .. } => Some((flex, max)),
+            WidgetSizeRequirement::Fixed { .. } |
+            WidgetSizeRequirement::None => None,
         }
-        
-        // Step 3: distribute flex space
+    }
 
-        let mut available_flex_space = available_space.get() - min_requirements;
-        let mut total_flex = total_flex;
-        let mut progress_have_been_made = true;
+    /// Distribute a given available_space between multiple requirements.
+    /// The algorithm will do it's best to respect all provided requirements, but it is sometimes impossible.
+    pub fn distribute_available_size<const N: usize>(requirements: [WidgetSizeRequirement; N], available_space: f32) -> [f32; N] {
+        let mut results = [0.0; N];
+        Self::distribute_available_size_into(&requirements, available_space, &mut results);
+        results
+    }
 
-        while progress_have_been_made {
+    /// Same as `distribute_available_size`, for a number of requirements that is only known at runtime.
+    /// The results slice must be as long as the requirements one.
+    ///
+    /// Every widget first gets its min size. If the min sizes do not fit, they are all reduced by the same ratio.
+    /// The remaining space is then shared by flex value between the widgets that can grow. A widget that reaches
+    /// its max size stops growing, and the space it does not take is shared between the others.
+    pub fn distribute_available_size_into(requirements: &[WidgetSizeRequirement], available_space: f32, results: &mut [f32]) {
+        debug_assert_eq!(requirements.len(), results.len());
+        let available_space = available_space.max(0.0);
 
-            // we keep giving space as long as there is:    
-            // - space to give
-            // - wigets than can accept more space (flex)
-            // - changes that have been made last iteration
+        // Step 1: give the min sizes, reduced if there is not enough space for them
 
-            progress_have_been_made = false;
-            if available_flex_space == 0 { break; }
-            let non_zero_total_flex = match NonZeroU32::new(total_flex) {
-                None => break,
-                Some(value) => value,
+        let min_requirements: f32 = requirements.iter().map(WidgetSizeRequirement::min_size).sum();
+        if min_requirements >= available_space {
+            let ratio = match min_requirements > 0.0 {
+                true => available_space / min_requirements,
+                false => 0.0,
             };
-            
-            
-            // creates an iterator over the space we computed for each flex unit.
-            // this iterator will repeat flex_val1 fv1 amount and flex_val2 fv2_amount
-            // this way, we are sure to distribute all flex space, taking remainder of euclidian div into account.
-            let mut flex_size_it = full_integer_div(available_flex_space, non_zero_total_flex);
-            
-            for (result, requirement) in results.iter_mut().zip(requirements) {
-                match requirement {
-                    // for widgets with max property : 
-                    // distribute flex, maxing to their max.
-                    // if we reach their max, remove their flex as part of the equation.
-                    WidgetSizeRequirement::Max { max, flex } | 
-                    WidgetSizeRequirement::MinMax { max, flex, .. } => {
-                        if *result < max.get() {
-                            // get the amount of flex space from the iterator according to our flex value
-                            let consumed = flex_size_it.next_flex(flex);
-                            if consumed >= *result - max.get() {
-                                total_flex -= flex.get();
-                                available_flex_space -= consumed;
-                                *result = max.get();
-                            }
-                            else {
-                                available_flex_space -= consumed;
-                                *result += consumed;
-                            }
-                            progress_have_been_made = true;
-                        }
-                    },
-                    // for purely flex widgets, 
-                    WidgetSizeRequirement::Flex { flex } |
-                    WidgetSizeRequirement::Min { flex, .. } => {
-                        let consumed = flex_size_it.next_flex(flex);
-                        available_flex_space -= consumed;
-                        *result += consumed;
-                        progress_have_been_made = true;
-                    },
-                    _ => {},
-                }
+            for (result, requirement) in results.iter_mut().zip(requirements.iter()) {
+                *result = requirement.min_size() * ratio;
             }
+            return;
+        }
+        for (result, requirement) in results.iter_mut().zip(requirements.iter()) {
+            *result = requirement.min_size();
         }
 
-        results
+        // Step 2: share the remaining space by flex values
+
+        let mut remaining_space = available_space - min_requirements;
+        let mut growing: Vec<usize> = (0..requirements.len()).filter(|index| requirements[*index].growth().is_some()).collect();
+        while remaining_space > 0.0 && !growing.is_empty() {
+            let total_flex: f32 = growing.iter().filter_map(|index| requirements[*index].growth()).map(|(flex, _)| flex.get() as f32).sum();
+            let space_per_flex = remaining_space / total_flex;
+            // widgets that would go over their max take it, and the space is shared again between the others
+            let before = growing.len();
+            growing.retain(|index| match requirements[*index].growth() {
+                Some((flex, max)) if results[*index] + space_per_flex * flex.get() as f32 >= max => {
+                    remaining_space -= (max - results[*index]).max(0.0);
+                    results[*index] = results[*index].max(max);
+                    false
+                },
+                _ => true,
+            });
+            if growing.len() == before {
+                for index in growing.iter() {
+                    if let Some((flex, _)) = requirements[*index].growth() {
+                        results[*index] += space_per_flex * flex.get() as f32;
+                    }
+                }
+                break;
+            }
+        }
     }
 }
 
 
-struct DividedAvailableFlexSpace {
-    part_size: u32,
-    big_part_count: u32,
-    small_part_count: u32,
-}
 
-impl DividedAvailableFlexSpace {
-    fn next_flex(&mut self, flex: NonZeroU32) -> u32 {
-        let mut flex_to_take = flex.get();
-        let taken_from_big = self.big_part_count.min(flex_to_take);
-        self.big_part_count -= taken_from_big;
-        flex_to_take -= taken_from_big;
-        let taken_from_small = self.small_part_count.min(flex_to_take);
-        self.small_part_count -= taken_from_small;
-        taken_from_big * (self.part_size + 1) + taken_from_small * self.part_size
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::WidgetSizeRequirement;
+
+    fn nz(value: u32) -> NonZeroU32 {
+        NonZeroU32::new(value).unwrap()
     }
-}
 
-/// Integer division, splitting the remainer into part of the results.
-/// The returned value is in the form ((res1, amount1), (res2, amount2))
-/// where the res1 is the quotient of the div, and
-/// - res1 * amount1 + res2 * amount2 = divedend,
-/// - res1 + 1 = res2
-/// - amount1 + amount 2 = divisor
-/// This basically performs an integer division, adding the remainder into part of the results
-/// This is usefull when splitting spaces onto widgets.
-fn full_integer_div(dividend: u32, divisor: NonZeroU32) -> DividedAvailableFlexSpace {
-    let quotient = dividend / divisor.get();
-    let remainder = dividend % divisor.get();
-    DividedAvailableFlexSpace {
-        part_size: quotient,
-        big_part_count: remainder,
-        small_part_count: divisor.get() - remainder,
+    fn fixed(size: u32) -> WidgetSizeRequirement {
+        WidgetSizeRequirement::Fixed { size: size as f32 }
+    }
+
+    fn flex(flex: u32) -> WidgetSizeRequirement {
+        WidgetSizeRequirement::Flex { flex: nz(flex) }
     }
-}
 
+    fn min(min: u32) -> WidgetSizeRequirement {
+        WidgetSizeRequirement::Min { min: min as f32, flex: nz(1) }
+    }
 
+    fn max(max: u32) -> WidgetSizeRequirement {
+        WidgetSizeRequirement::Max { max: max as f32, flex: nz(1) }
+    }
+
+    fn min_max(min: u32, max: u32) -> WidgetSizeRequirement {
+        WidgetSizeRequirement::MinMax { min: min as f32, max: max as f32, flex: nz(1) }
+    }
+
+    #[test]
+    fn none_is_neutral() {
+        for requirement in [fixed(10), flex(1), min(10), max(10), min_max(10, 20)] {
+            assert_eq!(requirement | WidgetSizeRequirement::None, requirement);
+            assert_eq!(WidgetSizeRequirement::None | requirement, requirement);
+            assert_eq!(requirement & WidgetSizeRequirement::None, requirement);
+            assert_eq!(WidgetSizeRequirement::None & requirement, requirement);
+        }
+    }
+
+    #[test]
+    fn bitor_fits_the_biggest_requirement() {
+        assert_eq!(fixed(10) | fixed(30), fixed(30));
+        assert_eq!(fixed(10) | flex(1), min(10));
+        assert_eq!(fixed(10) | min(30), min(30));
+        assert_eq!(min(30) | fixed(10), min(30));
+        assert_eq!(fixed(40) | max(30), min_max(40, 40));
+        assert_eq!(max(30) | fixed(10), min_max(10, 30));
+        assert_eq!(fixed(10) | min_max(20, 30), min_max(20, 30));
+        assert_eq!(min_max(20, 30) | fixed(40), min_max(40, 40));
+        assert_eq!(min(10) | min(20), WidgetSizeRequirement::Min { min: 20.0, flex: nz(2) });
+        assert_eq!(max(10) | max(20), WidgetSizeRequirement::Max { max: 20.0, flex: nz(2) });
+        assert_eq!(min_max(10, 40) | min_max(20, 30), WidgetSizeRequirement::MinMax { min: 20.0, max: 40.0, flex: nz(2) });
+    }
+
+    #[test]
+    fn bitor_with_flex_can_grow() {
+        assert_eq!(flex(1) | flex(2), flex(3));
+        assert_eq!(flex(1) | max(20), flex(2));
+        assert_eq!(flex(1) | min_max(10, 20), WidgetSizeRequirement::Min { min: 10.0, flex: nz(2) });
+    }
+
+    #[test]
+    fn bitand_sums_the_requirements() {
+        assert_eq!(fixed(10) & fixed(30), fixed(40));
+        assert_eq!(fixed(10) & flex(1), min(10));
+        assert_eq!(min(10) & min(20), WidgetSizeRequirement::Min { min: 30.0, flex: nz(2) });
+        assert_eq!(max(10) & max(20), WidgetSizeRequirement::Max { max: 30.0, flex: nz(2) });
+        assert_eq!(fixed(10) & max(20), min_max(10, 30));
+        assert_eq!(min_max(10, 20) & min_max(5, 5), WidgetSizeRequirement::MinMax { min: 15.0, max: 25.0, flex: nz(2) });
+    }
 
+    #[test]
+    fn add_grows_the_sizes() {
+        assert_eq!(fixed(10) + 5.0, fixed(15));
+        assert_eq!(flex(1) + 5.0, min(5));
+        assert_eq!(flex(1) + 0.0, flex(1));
+        assert_eq!(max(10) + 5.0, min_max(5, 15));
+        assert_eq!(min_max(10, 20) + 5.0, min_max(15, 25));
+        assert_eq!(WidgetSizeRequirement::None + 5.0, fixed(5));
+        assert_eq!(WidgetSizeRequirement::None + 0.0, WidgetSizeRequirement::None);
+    }
+
+    #[test]
+    fn at_most_limits_the_sizes() {
+        assert_eq!(fixed(10).at_most(5.0), fixed(5));
+        assert_eq!(fixed(10).at_most(20.0), fixed(10));
+        assert_eq!(flex(1).at_most(20.0), max(20));
+        assert_eq!(min(10).at_most(20.0), min_max(10, 20));
+        assert_eq!(min(30).at_most(20.0), fixed(20));
+        assert_eq!(max(30).at_most(20.0), max(20));
+        assert_eq!(min_max(10, 30).at_most(20.0), min_max(10, 20));
+        assert_eq!(WidgetSizeRequirement::None.at_most(20.0), WidgetSizeRequirement::None);
+    }
+
+    #[test]
+    fn min_and_max_constraints_combine() {
+        // this is how a constrained box combines its limits with the requirement of its child
+        let constrain = |child: WidgetSizeRequirement, lower: u32, upper: u32| (child | fixed(lower)).at_most(upper as f32);
+        assert_eq!(constrain(flex(1), 10, 20), min_max(10, 20));
+        assert_eq!(constrain(fixed(5), 10, 20), fixed(10));
+        assert_eq!(constrain(fixed(50), 10, 20), fixed(20));
+        assert_eq!(constrain(max(15), 10, 20), min_max(10, 15));
+    }
+
+    #[test]
+    fn resolve_stays_in_range() {
+        assert_eq!(fixed(10).resolve(0.0, 100.0), 10.0);
+        assert_eq!(fixed(200).resolve(0.0, 100.0), 100.0);
+        assert_eq!(flex(1).resolve(20.0, 100.0), 100.0);
+        assert_eq!(flex(1).resolve(20.0, crate::UNBOUNDED), 20.0);
+        assert_eq!(min(30).resolve(0.0, 100.0), 100.0);
+        assert_eq!(min(30).resolve(0.0, crate::UNBOUNDED), 30.0);
+        assert_eq!(max(30).resolve(0.0, 100.0), 30.0);
+        assert_eq!(min_max(10, 30).resolve(0.0, 20.0), 20.0);
+        assert_eq!(WidgetSizeRequirement::None.resolve(5.0, 100.0), 5.0);
+    }
+
+    #[test]
+    fn distribute_respects_fixed_sizes() {
+        let [left, right] = WidgetSizeRequirement::distribute_available_size([fixed(30), flex(1)], 100.0);
+        assert_eq!((left, right), (30.0, 70.0));
+    }
+
+    #[test]
+    fn distribute_shares_by_flex() {
+        let [left, right] = WidgetSizeRequirement::distribute_available_size([flex(1), flex(3)], 100.0);
+        assert_eq!((left, right), (25.0, 75.0));
+    }
+
+    #[test]
+    fn distribute_respects_max_sizes() {
+        let [left, right] = WidgetSizeRequirement::distribute_available_size([max(10), flex(1)], 100.0);
+        assert_eq!((left, right), (10.0, 90.0));
+    }
+
+    #[test]
+    fn distribute_shrinks_min_sizes_that_do_not_fit() {
+        let [left, right] = WidgetSizeRequirement::distribute_available_size([fixed(60), min(20)], 40.0);
+        assert_eq!((left, right), (30.0, 10.0));
+    }
+
+    #[test]
+    fn distribute_gives_all_the_space() {
+        // the space is not rounded, so nothing is lost when it does not divide evenly
+        let sizes = WidgetSizeRequirement::distribute_available_size([flex(1), flex(1), flex(1)], 100.0);
+        assert_eq!(sizes[0], sizes[2]);
+        assert!((sizes.iter().sum::<f32>() - 100.0).abs() < 1e-3);
+    }
+}
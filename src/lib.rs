@@ -1,5 +1,6 @@
 
 
+pub(crate) mod animation;
 pub(crate) mod app;
 pub(crate) mod drawing;
 pub(crate) mod geometry;
@@ -9,8 +10,23 @@ pub(crate) mod utils;
 // default app exports
 pub use app::{
     run_app,
-    event::AppEvent,
-    app_context::AppContext,
+    run_app_with_assets,
+    App,
+    delegate::AppDelegate,
+    error::NerfError,
+    window::WindowId,
+    windows::Windows,
+    assets::Assets,
+    event::{AppEvent, mouse_position::MousePosition},
+    event_sender::EventSender,
+    executor::{Executor, LocalExecutor, Task},
+    app_context::{AppContext, TimerId},
+};
+pub use animation::{
+    animated::Animation,
+    controller::AnimationController,
+    curve::Curve,
+    interpolate::Interpolate,
 };
 pub use drawing::{
     canvas::Canvas,
@@ -22,9 +38,14 @@ pub use geometry::{
         HorizontalAlignment,
         Alignment,
     },
+    constraints::{SizeConstraints, UNBOUNDED},
     decoration::BorderType,
+    edge_insets::EdgeInsets,
+    offset::Offset,
+    point::Point,
     rect::Rect,
     screen_side::ScreenSide,
+    size::Size,
     size_requirements::WidgetSizeRequirement,
 };
 pub use utils::nonable::Nonable;
@@ -33,20 +54,40 @@ pub use winit;
 // default widget exports
 pub use widget::{
     Widget,
+    layout::{LayoutContext, ChildRects},
     default_widgets::{
         align::Align,
+        animated_background::AnimatedBackground,
+        aspect_ratio::AspectRatio,
         background::Background,
-        button::Button,
+        button::{Button, ButtonResponse, ButtonState},
         center::Center,
+        constrained_box::ConstrainedBox,
         empty::Empty,
+        fractionally_sized_box::FractionallySizedBox,
+        grid::{Grid, GridCell, GridTrack},
+        list_view::{ListView, ListResponse},
         padder::{Padder, PaddType},
         sized_box::SizedBox,
+        stack::{Stack, positioned::Positioned},
+        wrap::Wrap,
         scaffold::Scaffold,
+        scroll_view::{ScrollView, ScrollAxis, ScrollResponse},
     },
 };
 // conditional exports
 #[cfg(feature = "text")]
+pub use app::assets::text_assets::{
+    TextAssets,
+    FontLoadingError,
+};
+#[cfg(feature = "text")]
 pub use widget::text_widgets::{
+    rich_text::{
+        RichText,
+        text_span::{TextSpan, SpanStyle},
+    },
+    selectable_text::{SelectableText, SelectionResponse},
     text::Text,
     text_style::{
         TextStyle,
@@ -54,5 +95,6 @@ pub use widget::text_widgets::{
         FontCharSpacing,
         FontStyle,
         font_family::FontFamily,
+        text_decoration::{TextDecoration, DecorationLine, DecorationStyle},
     },
 };
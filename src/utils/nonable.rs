@@ -19,3 +19,13 @@ impl<A: Nonable, B: Nonable> Nonable for (A, B) {
     }
 }
 
+
+/// Responses of multiple children, that is none if all of them are none.
+impl<T: Nonable> Nonable for Vec<T> {
+    fn none() -> Self {
+        Vec::new()
+    }
+    fn is_none(&self) -> bool {
+        self.iter().all(Nonable::is_none)
+    }
+}
@@ -1,9 +1,17 @@
 use crate::{
-    app::event::AppEvent, drawing::canvas::Canvas, geometry::size_requirements::WidgetSizeRequirement, utils::nonable::Nonable, Rect
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    utils::nonable::Nonable,
+    widget::layout::LayoutContext,
+    Point,
+    Rect,
+    Size,
 };
 
 
 pub(crate) mod default_widgets;
+pub(crate) mod layout;
 #[cfg(feature = "skia")]
 pub(crate) mod skia_widgets;
 #[cfg(feature = "text")]
@@ -19,15 +27,55 @@ pub trait Widget<UserEvent = ()> {
     type EventResponse: Nonable;
     /// Draw the widget on the canvas. The given rect is the area the widget should draw in, computed by its parent
     /// with it's size requirements. 
-    fn draw(&self, canvas: &mut Canvas, rect: Rect);
+    /// The context gives access to the app, for example to request another frame while the widget is animated.
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>);
     /// Get the size requirements of this widget.
     /// If the widgets requests sized outside of the constraints, they will be given smaller sizes to be drawn in.
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement);
+    /// Layout pass: choose the size of the widget within the constraints given by the parent, and return it.
+    /// Containers pass constraints down to their children, and can store the resulting child rects
+    /// to reuse them in `draw` and `handle_event` instead of recomputing them.
+    ///
+    /// The app runs the layout pass on the root once per frame, before drawing and before handling events if the tree changed.
+    /// Widgets must still be able to draw in a rect they have not been laid out with.
+    ///
+    /// By default, the widget takes the size its requirements give within the constraints.
+    fn layout(&mut self, constraints: SizeConstraints, _context: &mut LayoutContext) -> Size {
+        constraints.fit(self.min_space_requirements())
+    }
     /// Handles an event. Returns true if the event was handled, false otherwise.
     /// This will be called on the root, and need to be propagated down the Widget<UserEvent> for each custom widget implementation, 
     /// 
-    /// It is needed to recompute the widgets rect while doing so: events are called one after another, and there is no guarantee
-    /// that draw will be called between each event. As events can change widget layouts, it is needed to recompute the rect
-    /// to ensure that the next event is handled correctly. 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse;
-}
\ No newline at end of file
+    /// Child rects must be the same as the ones used to draw: containers can reuse the ones from the layout pass,
+    /// as the app lays out the tree again after an event got a response, before handling the next event.
+    ///
+    /// The context must be passed down to the children. Widgets can use it to change the cursor or the window title,
+    /// schedule timers, or post user events.
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse;
+    /// Whether the point is on the widget, for containers that only give the mouse to the widget under it.
+    ///
+    /// By default, the widget covers its whole rect. Widgets that only use a part of it can tell what is under them.
+    fn hit_test(&self, point: Point, rect: Rect) -> bool {
+        rect.contains(point)
+    }
+}
+
+/// Boxed widgets are widgets, so containers can hold children of different types as `Box<dyn Widget<UserEvent, EventResponse = R>>`.
+impl<UserEvent, W: Widget<UserEvent> + ?Sized> Widget<UserEvent> for Box<W> {
+    type EventResponse = W::EventResponse;
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.as_ref().draw(canvas, rect, context)
+    }
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.as_ref().min_space_requirements()
+    }
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        self.as_mut().layout(constraints, context)
+    }
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.as_mut().handle_event(event, rect, context)
+    }
+    fn hit_test(&self, point: Point, rect: Rect) -> bool {
+        self.as_ref().hit_test(point, rect)
+    }
+}
@@ -1,10 +1,10 @@
-use std::num::NonZeroU32;
-
 use crate::{
-    app::event::AppEvent, geometry::{
-        alignment::Alignment,
-        size_requirements::WidgetSizeRequirement
-    }, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent},
+    geometry::{alignment::Alignment, constraints::SizeConstraints},
+    widget::layout::{ChildRects, LayoutContext},
+    Rect,
+    Size,
+    Widget
 };
 
 
@@ -16,6 +16,7 @@ pub struct Align<UserEvent, Child: Widget<UserEvent>> {
     _m: core::marker::PhantomData<UserEvent>,
     child: Child,
     alignment: Alignment,
+    child_rect: ChildRects,
 }
 
 impl<UserEvent, Child: Widget<UserEvent>> Align<UserEvent, Child> {
@@ -24,53 +25,39 @@ impl<UserEvent, Child: Widget<UserEvent>> Align<UserEvent, Child> {
             _m: core::marker::PhantomData,
             child,
             alignment,
+            child_rect: ChildRects::new(),
         }
     }
 
-    fn get_child_and_remaining_size(child_requirement: WidgetSizeRequirement, available_size: NonZeroU32) -> (NonZeroU32, u32) {
-        match child_requirement {
-            // child can have any size, so we give it all the available space.
-            WidgetSizeRequirement::None |
-            WidgetSizeRequirement::Flex { .. } |
-            WidgetSizeRequirement::Min { .. } => (available_size, 0),
-            // child does not want to be bigger than a given size,
-            // so we give it the minimum between the available space and the size it wants.
-            WidgetSizeRequirement::Fixed { size: max, .. } |
-            WidgetSizeRequirement::Max { max, .. } |
-            WidgetSizeRequirement::MinMax { max, .. } => (
-                max.min(available_size),
-                available_size.get().checked_sub(max.get()).unwrap_or(0)
-            ),
-        }
-    }
-
-    fn compute_child_rect(&self, from_rect: softbuffer::Rect) -> softbuffer::Rect {
-        let (width_req, height_req) = self.child.min_space_requirements();
-        let (child_width, remaining_width) = Self::get_child_and_remaining_size(width_req, from_rect.width);
-        let (child_height, remaining_height) = Self::get_child_and_remaining_size(height_req, from_rect.height);
-        softbuffer::Rect {
-            x: from_rect.x + self.alignment.get_left_space(remaining_width),
-            y: from_rect.y + self.alignment.get_top_space(remaining_height),
-            width: child_width,
-            height: child_height,
+    fn compute_child_rect(&self, from_rect: Rect) -> Rect {
+        match self.child_rect.get_child(from_rect, 0) {
+            Some(child_rect) => child_rect,
+            None => self.alignment.place_child(self.child.min_space_requirements(), from_rect),
         }
     }
 }
 
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Align<UserEvent, Child> {
     type EventResponse = Child::EventResponse;
-    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect) {
-        let child_rect = self.compute_child_rect(rect);
-        self.child.draw(canvas, child_rect);
+    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, self.compute_child_rect(rect), context);
     }
 
     fn min_space_requirements(&self) -> (crate::geometry::size_requirements::WidgetSizeRequirement, crate::geometry::size_requirements::WidgetSizeRequirement) {
         self.child.min_space_requirements()
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        let child_rect = self.compute_child_rect(rect);
-        self.child.handle_event(event, child_rect)
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the child chooses its size in the available space, and is aligned in the remaining space
+        let size = constraints.fit(self.min_space_requirements());
+        let child_size = self.child.layout(SizeConstraints::loose(size.width, size.height), context);
+        let child_rect = self.alignment.place_size(child_size, Rect::from_size(size));
+        self.child_rect.store(size, vec![child_rect]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, self.compute_child_rect(rect), context)
     }
 }
 
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use crate::{
+    animation::{animated::Animation, curve::Curve}, app::{app_context::AppContext, event::AppEvent}, drawing::{canvas::Canvas, color::Color}, geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement}, widget::layout::LayoutContext, Rect, Size, Widget
+};
+
+
+/// Color to fade to after an event, given the response of the child.
+type ResponseColors<Response> = Box<dyn Fn(&Response) -> Option<Color>>;
+
+/// A background that fades to its new color when it changes.
+///
+/// The color can be set by the parent, or picked from the responses of the child with `with_response_colors`,
+/// for example to fade the background of a button when it is hovered and pressed.
+pub struct AnimatedBackground<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    color: Animation<Color>,
+    response_colors: ResponseColors<Child::EventResponse>,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> AnimatedBackground<UserEvent, Child> {
+    pub fn new(color: Color, duration: Duration, child: Child) -> Self {
+        AnimatedBackground {
+            _m: core::marker::PhantomData,
+            child,
+            color: Animation::new(color, duration),
+            response_colors: Box::new(|_| None),
+        }
+    }
+
+    pub fn with_curve(self, curve: Curve) -> Self {
+        AnimatedBackground { color: self.color.with_curve(curve), ..self }
+    }
+
+    /// Fade to the color returned for the response of the child, when there is one.
+    pub fn with_response_colors(self, response_colors: impl Fn(&Child::EventResponse) -> Option<Color> + 'static) -> Self {
+        AnimatedBackground { response_colors: Box::new(response_colors), ..self }
+    }
+
+    /// The color the background fades to.
+    pub fn color(&self) -> Color {
+        *self.color.target()
+    }
+
+    /// Fade to the new color.
+    pub fn set_color(&mut self, color: Color, context: &mut AppContext<UserEvent>) {
+        if color != self.color() {
+            self.color.animate_to(color, context);
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for AnimatedBackground<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        canvas.fill_rect(rect, self.color.value(context));
+        self.child.draw(canvas, rect, context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.child.min_space_requirements()
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        self.child.layout(constraints, context)
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let response = self.child.handle_event(event, rect, context);
+        if let Some(color) = (self.response_colors)(&response) {
+            self.set_color(color, context);
+        }
+        response
+    }
+}
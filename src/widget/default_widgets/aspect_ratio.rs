@@ -0,0 +1,143 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{alignment::Alignment, constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    widget::layout::{ChildRects, LayoutContext},
+    Rect,
+    Size,
+    Widget,
+};
+
+/// The aspect ratio widget gives its child the biggest rect with the given width / height ratio
+/// that fits in the available space, and aligns it in the remaining space.
+///
+/// When the child has a fixed width or height, the other one is required from the ratio.
+/// Otherwise, it fills the available space, and during the layout pass it takes the size of the child rect,
+/// so an `Align` or `Center` parent can place it.
+pub struct AspectRatio<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    /// Width divided by height.
+    ratio: f32,
+    alignment: Alignment,
+    child_rect: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> AspectRatio<UserEvent, Child> {
+    /// Create an aspect ratio widget, where the ratio is the width divided by the height.
+    pub fn new(ratio: f32, child: Child) -> Self {
+        assert!(ratio.is_finite() && ratio > 0.0, "Ratio of an aspect ratio widget must be positive");
+        AspectRatio {
+            _m: core::marker::PhantomData,
+            child,
+            ratio,
+            alignment: Alignment::CENTER,
+            child_rect: ChildRects::new(),
+        }
+    }
+
+    /// Alignment of the child when the available space does not have the same ratio. Default is centered.
+    pub fn aligned(self, alignment: Alignment) -> Self {
+        AspectRatio {
+            alignment,
+            ..self
+        }
+    }
+
+    fn height_for_width(&self, width: f32) -> f32 {
+        width / self.ratio
+    }
+
+    fn width_for_height(&self, height: f32) -> f32 {
+        height * self.ratio
+    }
+
+    /// Biggest size with the ratio that fits in the given size.
+    fn fit(&self, size: Size) -> Size {
+        let height_for_width = self.height_for_width(size.width);
+        match height_for_width <= size.height {
+            true => Size::new(size.width, height_for_width),
+            false => Size::new(self.width_for_height(size.height).min(size.width), size.height),
+        }
+    }
+
+    fn compute_child_rect(&self, rect: Rect) -> Rect {
+        match self.child_rect.get_child(rect, 0) {
+            Some(child_rect) => child_rect,
+            None => self.alignment.place_size(self.fit(rect.size()), rect),
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for AspectRatio<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, self.compute_child_rect(rect), context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        let fixed = |size: f32| WidgetSizeRequirement::Fixed { size };
+        match self.child.min_space_requirements() {
+            (WidgetSizeRequirement::Fixed { size }, _) => (fixed(size), fixed(self.height_for_width(size))),
+            (_, WidgetSizeRequirement::Fixed { size }) => (fixed(self.width_for_height(size)), fixed(size)),
+            _ => (
+                WidgetSizeRequirement::Flex { flex: NonZeroU32::MIN },
+                WidgetSizeRequirement::Flex { flex: NonZeroU32::MIN },
+            ),
+        }
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the size with the ratio that fits the constraints, using the bounded axis when the other one is not
+        let ratio_size = match (constraints.is_width_bounded(), constraints.is_height_bounded()) {
+            (true, true) => self.fit(Size::new(constraints.max_width, constraints.max_height)),
+            (true, false) => Size::new(constraints.max_width, self.height_for_width(constraints.max_width)),
+            (false, true) => Size::new(self.width_for_height(constraints.max_height), constraints.max_height),
+            (false, false) => constraints.fit(self.min_space_requirements()),
+        };
+        let size = constraints.constrain(ratio_size);
+        let child_rect = self.alignment.place_size(self.fit(size), Rect::from_size(size));
+        self.child.layout(SizeConstraints::tight_for(child_rect), context);
+        self.child_rect.store(size, vec![child_rect]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, self.compute_child_rect(rect), context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AspectRatio;
+    use crate::{Alignment, Assets, Empty, LayoutContext, Rect, SizeConstraints, Widget};
+
+    #[test]
+    fn child_takes_the_biggest_rect_with_the_ratio() {
+        let mut ratio: AspectRatio<(), _> = AspectRatio::new(2., Empty::expand());
+        let mut assets = Assets::new();
+        let size = ratio.layout(SizeConstraints::tight(300., 100.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (300., 100.));
+        assert_eq!(ratio.compute_child_rect(Rect::from_size(size)), Rect::new(50., 0., 200., 100.));
+    }
+
+    #[test]
+    fn loose_constraints_take_the_size_of_the_child() {
+        let mut ratio: AspectRatio<(), _> = AspectRatio::new(2., Empty::expand()).aligned(Alignment::TOP_LEFT);
+        let mut assets = Assets::new();
+        let size = ratio.layout(SizeConstraints::loose(300., 300.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (300., 150.));
+        assert_eq!(ratio.compute_child_rect(Rect::from_size(size)), Rect::new(0., 0., 300., 150.));
+    }
+
+    #[test]
+    fn unbounded_height_follows_the_width() {
+        let mut ratio: AspectRatio<(), _> = AspectRatio::new(4., Empty::expand());
+        let mut assets = Assets::new();
+        let size = ratio.layout(SizeConstraints::tight(200., 0.).with_unbounded_height(), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (200., 50.));
+    }
+}
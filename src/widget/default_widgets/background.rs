@@ -1,5 +1,5 @@
 use crate::{
-    app::event::AppEvent, drawing::{canvas::Canvas, color::Color}, geometry::size_requirements::WidgetSizeRequirement, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent}, drawing::{canvas::Canvas, color::Color}, geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement}, widget::layout::LayoutContext, Rect, Size, Widget
 };
 
 
@@ -28,16 +28,20 @@ impl<UserEvent, Child: Widget<UserEvent>> Background<UserEvent, Child> {
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Background<UserEvent, Child> {
     type EventResponse = Child::EventResponse;
 
-    fn draw(&self, canvas: &mut Canvas, rect: Rect) {
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
         canvas.fill_rect(rect, self.color);
-        self.child.draw(canvas, rect);
+        self.child.draw(canvas, rect, context);
     }
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
         self.child.min_space_requirements()       
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        self.child.handle_event(event, rect)
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        self.child.layout(constraints, context)
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, rect, context)
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 
 use crate::{
-    app::event::AppEvent, utils::nonable::Nonable, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent}, geometry::constraints::SizeConstraints, utils::nonable::Nonable, widget::layout::LayoutContext, Rect, Size, Widget
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,16 +51,20 @@ impl<UserEvent, Child: Widget<UserEvent>> Button<UserEvent, Child> {
 
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Button<UserEvent, Child> {
     type EventResponse = (ButtonResponse, Child::EventResponse);
-    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect) {
-        self.child.draw(canvas, rect);
+    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, rect, context);
     }
 
     fn min_space_requirements(&self) -> (crate::geometry::size_requirements::WidgetSizeRequirement, crate::geometry::size_requirements::WidgetSizeRequirement) {
         self.child.min_space_requirements()
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        let child_response = self.child.handle_event(event, rect);
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        self.child.layout(constraints, context)
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let child_response = self.child.handle_event(event, rect, context);
         let own_response = match (event, self.state) {
             (AppEvent::CursorMoved { position }, ButtonState::Idle) => if position.is_in_rect(rect) {
                 self.state = ButtonState::Hovered;
@@ -1,10 +1,10 @@
-use std::num::NonZeroU32;
-
 use crate::{
-    app::event::AppEvent, 
+    app::{app_context::AppContext, event::AppEvent}, 
     drawing::canvas::Canvas,
-    geometry::size_requirements::WidgetSizeRequirement,
+    geometry::{alignment::Alignment, constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    widget::layout::{ChildRects, LayoutContext},
     Rect,
+    Size,
     Widget
 };
 
@@ -14,6 +14,7 @@ use crate::{
 pub struct Center<UserEvent, Child: Widget<UserEvent>> {
     _m: core::marker::PhantomData<UserEvent>,
     child: Child,
+    child_rect: ChildRects,
 }
 
 impl<UserEvent, Child: Widget<UserEvent>> Center<UserEvent, Child> {
@@ -21,34 +22,38 @@ impl<UserEvent, Child: Widget<UserEvent>> Center<UserEvent, Child> {
         Center {
             _m: core::marker::PhantomData,
             child,
+            child_rect: ChildRects::new(),
         }
     }
 
-    fn get_child_size_and_spacing(child_requirement: WidgetSizeRequirement, available_size: NonZeroU32) -> (NonZeroU32, u32) {
+    fn get_child_size_and_spacing(child_requirement: WidgetSizeRequirement, available_size: f32) -> (f32, f32) {
         match child_requirement {
             // child can have any size, so we give it all the available space.
             WidgetSizeRequirement::None |
             WidgetSizeRequirement::Flex { .. } |
-            WidgetSizeRequirement::Min { .. } => (available_size, 0),
+            WidgetSizeRequirement::Min { .. } => (available_size, 0.0),
             // child does not want to be bigger than a given size,
             // so we give it the minimum between the available space and the size it wants.
             WidgetSizeRequirement::Fixed { size: max, .. } |
             WidgetSizeRequirement::Max { max, .. } |
             WidgetSizeRequirement::MinMax { max, .. } => (
                 max.min(available_size),
-                available_size.get().checked_sub(max.get()).unwrap_or(0) / 2
+                (available_size - max).max(0.0) / 2.0
             ),
         }
     }
 
-    fn compute_child_rect(&self, from_rect: softbuffer::Rect) -> softbuffer::Rect {
+    fn compute_child_rect(&self, from_rect: Rect) -> Rect {
+        if let Some(child_rect) = self.child_rect.get_child(from_rect, 0) {
+            return child_rect;
+        }
         let (
             width_requirement,
             height_requirement
         ) = self.child.min_space_requirements();
         let (width, width_spacing) = Self::get_child_size_and_spacing(width_requirement, from_rect.width);
         let (height, height_spacing) = Self::get_child_size_and_spacing(height_requirement, from_rect.height);
-        softbuffer::Rect {
+        Rect {
             x: from_rect.x + width_spacing,
             y: from_rect.y + height_spacing,
             width,
@@ -59,16 +64,23 @@ impl<UserEvent, Child: Widget<UserEvent>> Center<UserEvent, Child> {
 
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Center<UserEvent, Child> {
     type EventResponse = Child::EventResponse;
-    fn draw(&self, buffer: &mut Canvas, rect: Rect) {
-        let rect = self.compute_child_rect(rect);
-        self.child.draw(buffer, rect);
+    fn draw(&self, buffer: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(buffer, self.compute_child_rect(rect), context);
     }
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
         self.child.min_space_requirements()
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        self.child.handle_event(event, self.compute_child_rect(rect))
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let size = constraints.fit(self.min_space_requirements());
+        let child_size = self.child.layout(SizeConstraints::loose(size.width, size.height), context);
+        let child_rect = Alignment::CENTER.place_size(child_size, Rect::from_size(size));
+        self.child_rect.store(size, vec![child_rect]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, self.compute_child_rect(rect), context)
     }
 }
\ No newline at end of file
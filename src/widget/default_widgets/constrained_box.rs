@@ -0,0 +1,115 @@
+use crate::{
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    widget::layout::LayoutContext,
+    Rect,
+    Size,
+    Widget,
+};
+
+/// Min and max sizes imposed on one axis.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct AxisLimits {
+    min: Option<f32>,
+    max: Option<f32>,
+}
+
+impl AxisLimits {
+    /// Requirement of the child, that is at least min and at most max.
+    fn constrain(&self, child_requirement: WidgetSizeRequirement) -> WidgetSizeRequirement {
+        let requirement = match self.min {
+            // the min is a fixed requirement the child is overlapped with, so the result fits both
+            Some(min) => child_requirement | WidgetSizeRequirement::Fixed { size: min },
+            None => child_requirement,
+        };
+        match self.max {
+            Some(max) => requirement.at_most(max),
+            None => requirement,
+        }
+    }
+
+    /// Min and max of the parent constraints, restricted by the limits.
+    fn constrain_range(&self, min: f32, max: f32) -> (f32, f32) {
+        let limited_max = self.max.map(|limit| max.min(limit)).unwrap_or(max);
+        let limited_min = self.min.map(|limit| min.max(limit)).unwrap_or(min).min(max);
+        (limited_min, limited_max.max(limited_min))
+    }
+}
+
+/// The constrained box imposes min and / or max sizes to its child, on top of the child own requirements.
+/// For example, a flexible child with a max width will grow up to that width, and a small child with a min height
+/// will be given at least that height.
+/// 
+/// Like the `SizedBox`, it is NOT guaranteed that the child will respect the limits if the parent does not have enough space,
+/// or if the parent gives it more space than the max.
+pub struct ConstrainedBox<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    width: AxisLimits,
+    height: AxisLimits,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> ConstrainedBox<UserEvent, Child> {
+    /// Without any limit, the constrained box has the same requirements as its child.
+    pub fn new(child: Child) -> Self {
+        ConstrainedBox {
+            _m: core::marker::PhantomData,
+            child,
+            width: AxisLimits::default(),
+            height: AxisLimits::default(),
+        }
+    }
+
+    pub fn min_width(self, min_width: f32) -> Self {
+        ConstrainedBox {
+            width: AxisLimits { min: Some(min_width), ..self.width },
+            ..self
+        }
+    }
+
+    pub fn max_width(self, max_width: f32) -> Self {
+        ConstrainedBox {
+            width: AxisLimits { max: Some(max_width), ..self.width },
+            ..self
+        }
+    }
+
+    pub fn min_height(self, min_height: f32) -> Self {
+        ConstrainedBox {
+            height: AxisLimits { min: Some(min_height), ..self.height },
+            ..self
+        }
+    }
+
+    pub fn max_height(self, max_height: f32) -> Self {
+        ConstrainedBox {
+            height: AxisLimits { max: Some(max_height), ..self.height },
+            ..self
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for ConstrainedBox<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, rect, context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        let (width, height) = self.child.min_space_requirements();
+        (self.width.constrain(width), self.height.constrain(height))
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let (min_width, max_width) = self.width.constrain_range(constraints.min_width, constraints.max_width);
+        let (min_height, max_height) = self.height.constrain_range(constraints.min_height, constraints.max_height);
+        let size = self.child.layout(SizeConstraints { min_width, max_width, min_height, max_height }, context);
+        constraints.constrain(size)
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, rect, context)
+    }
+}
@@ -1,7 +1,7 @@
 use std::num::NonZeroU32;
 
 use crate::{
-    app::event::AppEvent, drawing::canvas::Canvas, geometry::size_requirements::WidgetSizeRequirement, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent}, drawing::canvas::Canvas, geometry::size_requirements::WidgetSizeRequirement, Rect, Widget
 };
 
 pub enum EmptyBehavior {
@@ -33,7 +33,7 @@ impl Empty {
 impl<UserEvent> Widget<UserEvent> for Empty {
     type EventResponse = ();
 
-    fn draw(&self, _buffer: &mut Canvas, _rect: Rect) {}
+    fn draw(&self, _buffer: &mut Canvas, _rect: Rect, _context: &mut AppContext<UserEvent>) {}
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
         match self.behavior {
@@ -45,5 +45,5 @@ impl<UserEvent> Widget<UserEvent> for Empty {
         }
     }
 
-    fn handle_event(&mut self, _: &AppEvent<UserEvent>, _: Rect) -> Self::EventResponse { }
+    fn handle_event(&mut self, _: &AppEvent<UserEvent>, _: Rect, _context: &mut AppContext<UserEvent>) -> Self::EventResponse { }
 }
\ No newline at end of file
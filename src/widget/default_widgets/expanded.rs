@@ -1,7 +1,7 @@
 use std::num::NonZeroU32;
 
 use crate::{
-    app::event::AppEvent, geometry::size_requirements::WidgetSizeRequirement, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent}, geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement}, widget::layout::LayoutContext, Rect, Size, Widget
 };
 
 
@@ -17,8 +17,8 @@ pub struct Expanded<UserEvent, Child: Widget<UserEvent>> {
 
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Expanded<UserEvent, Child> {
     type EventResponse = Child::EventResponse;
-    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect) {
-        self.child.draw(canvas, rect);
+    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, rect, context);
     }
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
@@ -28,8 +28,15 @@ impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Expanded<UserEve
         )
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        self.child.handle_event(event, rect)
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the expanded widget takes all the space, whatever the child size is
+        let size = constraints.fit(self.min_space_requirements());
+        self.child.layout(SizeConstraints::tight(size.width, size.height), context);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, rect, context)
     }
 }
 
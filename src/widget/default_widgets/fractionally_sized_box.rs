@@ -0,0 +1,153 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{alignment::Alignment, constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    widget::layout::{ChildRects, LayoutContext},
+    Rect,
+    Size,
+    Widget,
+};
+
+/// The fractionally sized box gives its child a fraction of the available width and / or height,
+/// and aligns it in the remaining space.
+/// On an axis without a factor, the child gets all the available space.
+///
+/// The box itself fills the available space on the axes with a factor, as its size is relative to its parent.
+pub struct FractionallySizedBox<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    width_factor: Option<f32>,
+    height_factor: Option<f32>,
+    alignment: Alignment,
+    child_rect: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> FractionallySizedBox<UserEvent, Child> {
+    pub fn new(width_factor: f32, height_factor: f32, child: Child) -> Self {
+        Self::with_factors(Some(width_factor), Some(height_factor), child)
+    }
+
+    pub fn width(width_factor: f32, child: Child) -> Self {
+        Self::with_factors(Some(width_factor), None, child)
+    }
+
+    pub fn height(height_factor: f32, child: Child) -> Self {
+        Self::with_factors(None, Some(height_factor), child)
+    }
+
+    fn with_factors(width_factor: Option<f32>, height_factor: Option<f32>, child: Child) -> Self {
+        let valid = |factor: Option<f32>| factor.map(|factor| factor.is_finite() && factor >= 0.0).unwrap_or(true);
+        assert!(valid(width_factor) && valid(height_factor), "Factors of a fractionally sized box must be positive");
+        FractionallySizedBox {
+            _m: core::marker::PhantomData,
+            child,
+            width_factor,
+            height_factor,
+            alignment: Alignment::CENTER,
+            child_rect: ChildRects::new(),
+        }
+    }
+
+    /// Alignment of the child in the remaining space. Default is centered.
+    pub fn aligned(self, alignment: Alignment) -> Self {
+        FractionallySizedBox {
+            alignment,
+            ..self
+        }
+    }
+
+    fn fraction(factor: Option<f32>, available: f32) -> f32 {
+        match factor {
+            Some(factor) => available * factor,
+            None => available,
+        }
+    }
+
+    fn compute_child_rect(&self, rect: Rect) -> Rect {
+        match self.child_rect.get_child(rect, 0) {
+            Some(child_rect) => child_rect,
+            None => self.alignment.place_size(
+                Size::new(Self::fraction(self.width_factor, rect.width), Self::fraction(self.height_factor, rect.height)),
+                rect,
+            ),
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for FractionallySizedBox<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, self.compute_child_rect(rect), context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        let (child_width, child_height) = self.child.min_space_requirements();
+        let flex = WidgetSizeRequirement::Flex { flex: NonZeroU32::MIN };
+        (
+            if self.width_factor.is_some() { flex } else { child_width },
+            if self.height_factor.is_some() { flex } else { child_height },
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // a fraction of an unbounded axis has no meaning, so the child chooses its size on it
+        let axis = |factor: Option<f32>, min: f32, max: f32, bounded: bool| match (factor, bounded) {
+            (Some(factor), true) => {
+                let size = Self::fraction(Some(factor), max);
+                (size, size)
+            },
+            (Some(_), false) => (0.0, max),
+            (None, _) => (min, max),
+        };
+        let (min_width, max_width) = axis(self.width_factor, constraints.min_width, constraints.max_width, constraints.is_width_bounded());
+        let (min_height, max_height) = axis(self.height_factor, constraints.min_height, constraints.max_height, constraints.is_height_bounded());
+        let child_size = self.child.layout(SizeConstraints { min_width, max_width, min_height, max_height }, context);
+        let size = constraints.constrain(Size {
+            width: if self.width_factor.is_some() && constraints.is_width_bounded() { constraints.max_width } else { child_size.width },
+            height: if self.height_factor.is_some() && constraints.is_height_bounded() { constraints.max_height } else { child_size.height },
+        });
+        let child_rect = self.alignment.place_size(child_size, Rect::from_size(size));
+        self.child_rect.store(size, vec![child_rect]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, self.compute_child_rect(rect), context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FractionallySizedBox;
+    use crate::{Alignment, Assets, Empty, LayoutContext, Rect, SizeConstraints, SizedBox, Widget};
+
+    #[test]
+    fn child_gets_a_fraction_of_the_space() {
+        let mut fraction: FractionallySizedBox<(), _> = FractionallySizedBox::new(0.5, 0.25, Empty::expand());
+        let mut assets = Assets::new();
+        let size = fraction.layout(SizeConstraints::tight(200., 100.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (200., 100.));
+        assert_eq!(fraction.compute_child_rect(Rect::from_size(size)), Rect::new(50., 37.5, 100., 25.));
+    }
+
+    #[test]
+    fn axis_without_factor_takes_the_size_of_the_child() {
+        let mut fraction: FractionallySizedBox<(), _> =
+            FractionallySizedBox::width(0.5, SizedBox::new(40., 20., Empty::expand())).aligned(Alignment::TOP_LEFT);
+        let mut assets = Assets::new();
+        let size = fraction.layout(SizeConstraints::loose(200., 100.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (200., 20.));
+        assert_eq!(fraction.compute_child_rect(Rect::from_size(size)), Rect::new(0., 0., 100., 20.));
+    }
+
+    #[test]
+    fn unbounded_axis_with_factor_takes_the_size_of_the_child() {
+        let mut fraction: FractionallySizedBox<(), _> = FractionallySizedBox::new(0.5, 0.5, SizedBox::new(40., 20., Empty::expand()));
+        let mut assets = Assets::new();
+        let size = fraction.layout(SizeConstraints::tight(200., 0.).with_unbounded_height(), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (200., 20.));
+    }
+}
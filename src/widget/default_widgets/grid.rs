@@ -0,0 +1,303 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{
+        alignment::Alignment,
+        constraints::SizeConstraints,
+        edge_insets::EdgeInsets,
+        size_requirements::WidgetSizeRequirement,
+    },
+    widget::layout::{ChildRects, LayoutContext},
+    Rect,
+    Size,
+    Widget,
+};
+
+/// Sizing of a row or a column of a grid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GridTrack {
+    /// The track has a fixed size, in logical pixels.
+    Fixed(f32),
+    /// The track shares the remaining space with the other flex tracks, according to its flex value.
+    Flex(u32),
+    /// The track takes the size required by its cells. Cells spanning multiple tracks are not taken into account.
+    Auto,
+    /// The track is at least min pixels, and shares the remaining space up to max pixels.
+    /// A max of 0 means there is no maximum.
+    MinMax {
+        min: f32,
+        max: f32,
+        flex: u32,
+    },
+}
+
+impl GridTrack {
+    fn requirement(&self, cells_requirement: impl FnOnce() -> WidgetSizeRequirement) -> WidgetSizeRequirement {
+        match *self {
+            GridTrack::Fixed(size) => WidgetSizeRequirement::Fixed { size },
+            GridTrack::Flex(flex) => match NonZeroU32::new(flex) {
+                Some(flex) => WidgetSizeRequirement::Flex { flex },
+                None => WidgetSizeRequirement::None,
+            },
+            GridTrack::Auto => cells_requirement(),
+            GridTrack::MinMax { min, max, flex } => {
+                let flex = NonZeroU32::new(flex).unwrap_or(NonZeroU32::MIN);
+                match (min > 0.0, max > 0.0) {
+                    (true, true) => WidgetSizeRequirement::MinMax { min, max: max.max(min), flex },
+                    (false, true) => WidgetSizeRequirement::Max { max, flex },
+                    (true, false) => WidgetSizeRequirement::Min { min, flex },
+                    (false, false) => WidgetSizeRequirement::None,
+                }
+            },
+        }
+    }
+}
+
+/// A child of a grid, with the tracks it is placed in.
+pub struct GridCell<Child> {
+    pub child: Child,
+    pub row: usize,
+    pub column: usize,
+    pub row_span: usize,
+    pub column_span: usize,
+    /// If set, the child is aligned in the cell with the size it requires instead of filling it.
+    pub alignment: Option<Alignment>,
+    /// Space left empty around the child, inside the cell.
+    pub margin: EdgeInsets,
+}
+
+impl<Child> GridCell<Child> {
+    pub fn new(row: usize, column: usize, child: Child) -> Self {
+        GridCell {
+            child,
+            row,
+            column,
+            row_span: 1,
+            column_span: 1,
+            alignment: None,
+            margin: EdgeInsets::ZERO,
+        }
+    }
+
+    pub fn spanning(self, row_span: usize, column_span: usize) -> Self {
+        GridCell {
+            row_span: row_span.max(1),
+            column_span: column_span.max(1),
+            ..self
+        }
+    }
+
+    pub fn aligned(self, alignment: Alignment) -> Self {
+        GridCell {
+            alignment: Some(alignment),
+            ..self
+        }
+    }
+
+    pub fn with_margin(self, margin: EdgeInsets) -> Self {
+        GridCell {
+            margin,
+            ..self
+        }
+    }
+
+    /// Requirements of the child with its margin around it.
+    fn requirements<UserEvent>(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) where Child: Widget<UserEvent> {
+        self.margin.inflate_requirements(self.child.min_space_requirements())
+    }
+}
+
+/// The grid places its children in cells, at the crossing of rows and columns.
+/// Each row and column is sized with a track definition, and children can span multiple tracks.
+///
+/// All children have the same type. To use different widgets in the same grid,
+/// use `Box<dyn Widget<UserEvent, EventResponse = R>>` as the child type.
+pub struct Grid<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    columns: Vec<GridTrack>,
+    rows: Vec<GridTrack>,
+    column_gap: f32,
+    row_gap: f32,
+    cells: Vec<GridCell<Child>>,
+    cell_rects: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Grid<UserEvent, Child> {
+    pub fn new(columns: Vec<GridTrack>, rows: Vec<GridTrack>) -> Self {
+        Grid {
+            _m: core::marker::PhantomData,
+            columns,
+            rows,
+            column_gap: 0.0,
+            row_gap: 0.0,
+            cells: Vec::new(),
+            cell_rects: ChildRects::new(),
+        }
+    }
+
+    pub fn with_gaps(self, column_gap: f32, row_gap: f32) -> Self {
+        Grid {
+            column_gap,
+            row_gap,
+            ..self
+        }
+    }
+
+    pub fn with_cell(self, cell: GridCell<Child>) -> Self {
+        let mut cells = self.cells;
+        cells.push(cell);
+        Grid {
+            cells,
+            ..self
+        }
+    }
+
+    /// Cells of the grid, to add, remove or move them.
+    /// The grid computes its cell rects again on next use, but its ancestors keep their layout:
+    /// call `AppContext::request_layout` so the window is laid out again.
+    pub fn cells_mut(&mut self) -> &mut Vec<GridCell<Child>> {
+        self.cell_rects.invalidate();
+        &mut self.cells
+    }
+
+    /// Requirements of the tracks of an axis, with the gaps between them as fixed requirements.
+    fn track_requirements(&self, horizontal: bool) -> Vec<WidgetSizeRequirement> {
+        let (tracks, gap) = match horizontal {
+            true => (&self.columns, self.column_gap),
+            false => (&self.rows, self.row_gap),
+        };
+        let gap = WidgetSizeRequirement::Fixed { size: gap };
+        let mut requirements = Vec::with_capacity(tracks.len() * 2);
+        for (index, track) in tracks.iter().enumerate() {
+            if index > 0 {
+                requirements.push(gap);
+            }
+            requirements.push(track.requirement(|| {
+                // cells in the same track are next to each other along the other axis, so the track needs to fit all of them
+                self.cells.iter()
+                    .filter(|cell| match horizontal {
+                        true => cell.column == index && cell.column_span == 1,
+                        false => cell.row == index && cell.row_span == 1,
+                    })
+                    .map(|cell| match horizontal {
+                        true => cell.requirements().0,
+                        false => cell.requirements().1,
+                    })
+                    .fold(WidgetSizeRequirement::None, |acc, requirement| acc | requirement)
+            }));
+        }
+        requirements
+    }
+
+    /// Start position of each track and gap of an axis, plus the end of the last one.
+    fn track_positions(&self, horizontal: bool, start: f32, available_space: f32) -> Vec<f32> {
+        let requirements = self.track_requirements(horizontal);
+        let mut sizes = vec![0.0; requirements.len()];
+        WidgetSizeRequirement::distribute_available_size_into(&requirements, available_space, &mut sizes);
+        let mut positions = Vec::with_capacity(sizes.len() + 1);
+        let mut position = start;
+        positions.push(position);
+        for size in sizes {
+            position += size;
+            positions.push(position);
+        }
+        positions
+    }
+
+    /// Rect of each cell. Cells outside of the grid get an empty rect at its origin.
+    fn cell_rects(&self, rect: Rect) -> Vec<Rect> {
+        if let Some(rects) = self.cell_rects.get(rect) {
+            return rects;
+        }
+        let columns = self.track_positions(true, rect.x, rect.width);
+        let rows = self.track_positions(false, rect.y, rect.height);
+        // tracks are at even indices in the positions, gaps in between
+        let span = |positions: &Vec<f32>, track_count: usize, index: usize, span: usize| -> Option<(f32, f32)> {
+            if index >= track_count {
+                return None;
+            }
+            let last = (index + span).min(track_count) - 1;
+            let start = positions[index * 2];
+            let end = positions[last * 2 + 1];
+            Some((start, end - start))
+        };
+        self.cells.iter().map(|cell| {
+            let (x, width) = span(&columns, self.columns.len(), cell.column, cell.column_span).unwrap_or((rect.x, 0.0));
+            let (y, height) = span(&rows, self.rows.len(), cell.row, cell.row_span).unwrap_or((rect.y, 0.0));
+            let cell_rect = cell.margin.deflate(Rect { x, y, width, height });
+            match cell.alignment {
+                Some(alignment) => alignment.place_child(cell.child.min_space_requirements(), cell_rect),
+                None => cell_rect,
+            }
+        }).collect()
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Grid<UserEvent, Child> {
+    /// The responses of the cells, in the order they were added.
+    type EventResponse = Vec<Child::EventResponse>;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        for (cell, cell_rect) in self.cells.iter().zip(self.cell_rects(rect)) {
+            cell.child.draw(canvas, cell_rect, context);
+        }
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        // tracks and gaps follow each other on each axis
+        let sum = |requirements: Vec<WidgetSizeRequirement>| requirements.into_iter()
+            .fold(WidgetSizeRequirement::None, |acc, requirement| acc & requirement);
+        (sum(self.track_requirements(true)), sum(self.track_requirements(false)))
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let size = constraints.fit(self.min_space_requirements());
+        self.cell_rects.invalidate();
+        let rects = self.cell_rects(Rect::from_size(size));
+        for (cell, cell_rect) in self.cells.iter_mut().zip(rects.iter()) {
+            cell.child.layout(SizeConstraints::tight_for(*cell_rect), context);
+        }
+        self.cell_rects.store(size, rects);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let cell_rects = self.cell_rects(rect);
+        self.cells.iter_mut().zip(cell_rects).map(|(cell, cell_rect)| cell.child.handle_event(event, cell_rect, context)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::{Grid, GridCell, GridTrack};
+    use crate::{Empty, Rect, SizedBox, WidgetSizeRequirement, Widget};
+
+    fn cell(row: usize, column: usize, width: f32) -> GridCell<SizedBox<(), Empty>> {
+        GridCell::new(row, column, SizedBox::width(width, Empty::expand()))
+    }
+
+    #[test]
+    fn auto_track_fits_its_widest_cell() {
+        // cells of the same column are on top of each other, so their widths overlap instead of adding up
+        let grid: Grid<(), _> = Grid::new(vec![GridTrack::Auto, GridTrack::Flex(1)], vec![GridTrack::Flex(1), GridTrack::Flex(1)])
+            .with_cell(cell(0, 0, 30.))
+            .with_cell(cell(1, 0, 50.))
+            .with_cell(cell(0, 1, 10.));
+        let rects = grid.cell_rects(Rect::new(0., 0., 200., 100.));
+        assert_eq!(rects[0], Rect::new(0., 0., 50., 50.));
+        assert_eq!(rects[1], Rect::new(0., 50., 50., 50.));
+        assert_eq!(rects[2], Rect::new(50., 0., 150., 50.));
+        assert_eq!(grid.min_space_requirements().0, WidgetSizeRequirement::Min { min: 50., flex: NonZeroU32::MIN });
+    }
+
+    #[test]
+    fn auto_track_without_cells_is_empty() {
+        let grid: Grid<(), _> = Grid::new(vec![GridTrack::Auto, GridTrack::Flex(1)], vec![GridTrack::Flex(1)])
+            .with_cell(cell(0, 1, 10.));
+        assert_eq!(grid.cell_rects(Rect::new(0., 0., 200., 100.)), vec![Rect::new(0., 0., 200., 100.)]);
+    }
+}
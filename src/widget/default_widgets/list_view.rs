@@ -0,0 +1,378 @@
+use std::{cell::RefCell, collections::HashMap, num::NonZeroU32};
+
+use winit::event::MouseScrollDelta;
+
+use crate::{
+    app::{
+        app_context::AppContext,
+        event::{mouse_position::MousePosition, AppEvent},
+    },
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    utils::nonable::Nonable,
+    widget::layout::LayoutContext,
+    Rect,
+    Size,
+    Widget,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListResponse<ItemResponse> {
+    /// Whether the list got scrolled.
+    pub scrolled: bool,
+    /// Responses of the items that responded to the event, with the index of the item.
+    pub items: Vec<(usize, ItemResponse)>,
+}
+
+impl<ItemResponse> Nonable for ListResponse<ItemResponse> {
+    fn none() -> Self {
+        ListResponse { scrolled: false, items: Vec::new() }
+    }
+    fn is_none(&self) -> bool {
+        !self.scrolled && self.items.is_empty()
+    }
+}
+
+/// Item widgets that are alive, and the ones that can be reused.
+struct ItemCache<Item, Builder> {
+    builder: Builder,
+    /// Items that have been built, by index.
+    alive: HashMap<usize, Item>,
+    /// Items that are no longer shown, given back to the builder when a new item is needed.
+    recycled: Vec<Item>,
+}
+
+impl<Item, Builder: FnMut(usize, Option<Item>) -> Item> ItemCache<Item, Builder> {
+    fn item(&mut self, index: usize) -> &mut Item {
+        self.alive.entry(index).or_insert_with(|| (self.builder)(index, self.recycled.pop()))
+    }
+
+    /// Height the item takes in the list. The item is built if needed, and kept alive.
+    fn height<UserEvent>(&mut self, index: usize, default_height: f32) -> f32 where Item: Widget<UserEvent> {
+        item_height(self.item(index), default_height)
+    }
+
+    /// Height the item takes in the list, without keeping it alive.
+    /// Items that are not alive are built from a recycled item to be measured and recycled right away,
+    /// so scrolling over many items does not build them all.
+    fn measure<UserEvent>(&mut self, index: usize, default_height: f32) -> f32 where Item: Widget<UserEvent> {
+        if let Some(item) = self.alive.get(&index) {
+            return item_height(item, default_height);
+        }
+        let item = (self.builder)(index, self.recycled.pop());
+        let height = item_height(&item, default_height);
+        self.recycled.push(item);
+        height
+    }
+
+    /// Recycle all the items that are not in the given range.
+    fn keep_only(&mut self, indices: std::ops::Range<usize>) {
+        let hidden: Vec<usize> = self.alive.keys().copied().filter(|index| !indices.contains(index)).collect();
+        for index in hidden {
+            if let Some(item) = self.alive.remove(&index) {
+                self.recycled.push(item);
+            }
+        }
+    }
+
+    /// Recycle all items, so they are built again.
+    fn clear(&mut self) {
+        self.recycled.extend(self.alive.drain().map(|(_, item)| item));
+    }
+}
+
+/// Height an item takes in the list: the height it requires, or the default height if it is flexible.
+fn item_height<UserEvent>(item: &impl Widget<UserEvent>, default_height: f32) -> f32 {
+    match item.min_space_requirements().1 {
+        WidgetSizeRequirement::Fixed { size } => size,
+        WidgetSizeRequirement::Min { min, .. } => min,
+        WidgetSizeRequirement::MinMax { min, .. } => min,
+        WidgetSizeRequirement::Max { max, .. } => max.min(default_height),
+        WidgetSizeRequirement::Flex { .. } => default_height,
+        WidgetSizeRequirement::None => default_height,
+    }
+}
+
+/// A vertical list of items, that only builds and draws the visible items.
+/// This allows to display very long lists, as the cost only depends on the number of visible items.
+///
+/// Items are created by the builder, from their index. When an item scrolls out of view,
+/// its widget is kept and given back to the builder for another index, so it can be updated instead of rebuilt.
+///
+/// Each item takes the height it requires (fixed or min size), or the default item height if it is flexible.
+pub struct ListView<UserEvent, Item: Widget<UserEvent>, Builder: FnMut(usize, Option<Item>) -> Item> {
+    _m: core::marker::PhantomData<UserEvent>,
+    item_count: usize,
+    /// The items are built when laid out or drawn, once we know which ones are visible.
+    cache: RefCell<ItemCache<Item, Builder>>,
+    /// Index of the first visible item.
+    first_item: usize,
+    /// Pixels of the first visible item that are hidden above the list. Fractional to add up small touchpad movements.
+    first_item_offset: f32,
+    default_item_height: f32,
+    /// Amount of pixels scrolled for one line of mouse wheel.
+    line_height: f32,
+    mouse_position: MousePosition,
+}
+
+impl<UserEvent, Item: Widget<UserEvent>, Builder: FnMut(usize, Option<Item>) -> Item> ListView<UserEvent, Item, Builder> {
+    pub fn new(item_count: usize, builder: Builder) -> Self {
+        ListView {
+            _m: core::marker::PhantomData,
+            item_count,
+            cache: RefCell::new(ItemCache { builder, alive: HashMap::new(), recycled: Vec::new() }),
+            first_item: 0,
+            first_item_offset: 0.,
+            default_item_height: 30.,
+            line_height: 20.,
+            mouse_position: MousePosition::OutOfWindow,
+        }
+    }
+
+    pub fn with_default_item_height(self, default_item_height: f32) -> Self {
+        assert!(default_item_height > 0., "Default item height of a list view must be positive");
+        ListView {
+            default_item_height,
+            ..self
+        }
+    }
+
+    pub fn with_line_height(self, line_height: f32) -> Self {
+        ListView {
+            line_height,
+            ..self
+        }
+    }
+
+    pub fn item_count(&self) -> usize {
+        self.item_count
+    }
+
+    /// Change the number of items. The existing items are kept, call `refresh` if their content changed,
+    /// and `AppContext::request_layout` so the visible items are laid out again.
+    pub fn set_item_count(&mut self, item_count: usize) {
+        self.item_count = item_count;
+        if self.first_item >= item_count {
+            self.first_item = item_count.saturating_sub(1);
+            self.first_item_offset = 0.;
+        }
+    }
+
+    /// Build all the items again, for when the data they display changed.
+    pub fn refresh(&mut self) {
+        self.cache.get_mut().clear();
+    }
+
+    /// Index of the first visible item.
+    pub fn first_visible_item(&self) -> usize {
+        self.first_item
+    }
+
+    /// Scroll so the given item is at the top of the list.
+    pub fn scroll_to(&mut self, index: usize) {
+        self.first_item = index.min(self.item_count.saturating_sub(1));
+        self.first_item_offset = 0.;
+    }
+
+    /// Rects of the visible items. The first visible item starts above the view by the part of it that is scrolled out.
+    /// Items that are not visible anymore are recycled.
+    fn visible_items(&self, cache: &mut ItemCache<Item, Builder>, viewport: Rect) -> Vec<(usize, Rect)> {
+        let mut rects = Vec::new();
+        let mut y = viewport.y - self.first_item_offset;
+        let mut index = self.first_item;
+        while y < viewport.bottom() && index < self.item_count {
+            let height = cache.height(index, self.default_item_height);
+            rects.push((index, Rect::new(viewport.x, y, viewport.width, height)));
+            y += height;
+            index += 1;
+        }
+        cache.keep_only(self.first_item..index);
+        rects
+    }
+
+    /// Scroll by the given amount of pixels, going through the items one by one.
+    fn scroll_by(&mut self, delta: f32, viewport: Rect) -> bool {
+        let previous = (self.first_item, self.first_item_offset);
+        let default_height = self.default_item_height;
+        let cache = self.cache.get_mut();
+        self.first_item_offset += delta;
+        // going down, skip the items that are fully hidden
+        while self.first_item + 1 < self.item_count {
+            let height = cache.measure(self.first_item, default_height);
+            if self.first_item_offset < height {
+                break;
+            }
+            self.first_item_offset -= height;
+            self.first_item += 1;
+        }
+        // going up, show the previous items
+        while self.first_item_offset < 0. && self.first_item > 0 {
+            self.first_item -= 1;
+            self.first_item_offset += cache.measure(self.first_item, default_height);
+        }
+        self.first_item_offset = self.first_item_offset.max(0.);
+        // don't scroll past the last item: if the items below do not fill the view, go back up.
+        let mut remaining = -self.first_item_offset;
+        let mut index = self.first_item;
+        while remaining < viewport.height && index < self.item_count {
+            remaining += cache.measure(index, default_height);
+            index += 1;
+        }
+        let mut missing = viewport.height - remaining;
+        if missing > 0. {
+            while missing > self.first_item_offset && self.first_item > 0 {
+                missing -= self.first_item_offset;
+                self.first_item -= 1;
+                self.first_item_offset = cache.measure(self.first_item, default_height);
+            }
+            self.first_item_offset = (self.first_item_offset - missing).max(0.);
+        }
+        previous != (self.first_item, self.first_item_offset)
+    }
+
+    /// Mouse position given to the items. The items are hidden outside of the view, so the mouse can't be on them there.
+    fn content_mouse_position(&self, viewport: Rect) -> MousePosition {
+        match self.mouse_position.is_in_rect(viewport) {
+            true => self.mouse_position,
+            false => MousePosition::OutOfWindow,
+        }
+    }
+}
+
+impl<UserEvent, Item: Widget<UserEvent>, Builder: FnMut(usize, Option<Item>) -> Item> Widget<UserEvent> for ListView<UserEvent, Item, Builder> {
+    type EventResponse = ListResponse<Item::EventResponse>;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        let mut cache = self.cache.borrow_mut();
+        let rects = self.visible_items(&mut cache, rect);
+        canvas.with_clip(rect, |canvas| {
+            for (index, item_rect) in rects {
+                cache.item(index).draw(canvas, item_rect, context);
+            }
+        });
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        (
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // only the visible items are laid out, scrolling requests another pass for the ones scrolled into view
+        let size = constraints.fit(self.min_space_requirements());
+        let mut cache = self.cache.borrow_mut();
+        for (index, item_rect) in self.visible_items(&mut cache, Rect::from_size(size)) {
+            cache.item(index).layout(SizeConstraints::tight_for(item_rect), context);
+        }
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let scrolled = match event {
+            AppEvent::CursorMoved { position } => {
+                self.mouse_position = *position;
+                false
+            },
+            AppEvent::MouseWheel { delta, .. } if self.mouse_position.is_in_rect(rect) => {
+                let delta = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => -y * self.line_height,
+                    MouseScrollDelta::PixelDelta(position) => -position.y as f32,
+                };
+                self.scroll_by(delta, rect)
+            },
+            _ => false,
+        };
+        if scrolled {
+            context.request_layout();
+        }
+
+        // items are hidden outside of the view, so is the mouse.
+        // after scrolling, the items moved under the mouse, so they are told where it is now.
+        let content_position = self.content_mouse_position(rect);
+        let item_event = match (event, scrolled) {
+            (AppEvent::CursorMoved { .. }, _) | (AppEvent::MouseWheel { .. }, true) => Some(AppEvent::CursorMoved { position: content_position }),
+            _ => None,
+        };
+        let event = item_event.as_ref().unwrap_or(event);
+
+        let mut cache = self.cache.borrow_mut();
+        let rects = self.visible_items(&mut cache, rect);
+        let items = rects.into_iter().filter_map(|(index, item_rect)| {
+            let response = cache.item(index).handle_event(event, item_rect, context);
+            match response.is_none() {
+                true => None,
+                false => Some((index, response)),
+            }
+        }).collect();
+
+        ListResponse { scrolled, items }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::ListView;
+    use crate::{Empty, Rect, SizedBox, Widget};
+
+    const VIEWPORT: Rect = Rect::new(0., 0., 100., 100.);
+
+    type Item = SizedBox<(), Empty>;
+
+    /// List of 30 pixel high items, that counts the items built without a recycled one.
+    fn list(item_count: usize, built: Rc<Cell<usize>>) -> ListView<(), Item, impl FnMut(usize, Option<Item>) -> Item> {
+        ListView::new(item_count, move |_, recycled| match recycled {
+            Some(item) => item,
+            None => {
+                built.set(built.get() + 1);
+                SizedBox::height(30., Empty::expand())
+            },
+        })
+    }
+
+    fn visible<I: Widget<()>, B: FnMut(usize, Option<I>) -> I>(list: &ListView<(), I, B>) -> Vec<(usize, Rect)> {
+        list.visible_items(&mut list.cache.borrow_mut(), VIEWPORT)
+    }
+
+    #[test]
+    fn only_visible_items_are_placed() {
+        let mut list = list(100, Rc::new(Cell::new(0)));
+        let rects = visible(&list);
+        assert_eq!(rects.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+        assert_eq!(rects[3].1, Rect::new(0., 90., 100., 30.));
+
+        // the first item is partly scrolled out, above the view
+        list.scroll_by(45., VIEWPORT);
+        let rects = visible(&list);
+        assert_eq!(rects.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+        assert_eq!(rects[0].1, Rect::new(0., -15., 100., 30.));
+    }
+
+    #[test]
+    fn scrolled_out_items_are_recycled() {
+        let built = Rc::new(Cell::new(0));
+        let mut list = list(10_000, built.clone());
+        visible(&list);
+        assert_eq!(built.get(), 4);
+        // a long scroll measures all the items it goes through, with the same few widgets
+        list.scroll_by(100_000., VIEWPORT);
+        visible(&list);
+        assert_eq!(list.cache.get_mut().alive.len(), 4);
+        // the new visible items are built before the hidden ones are recycled
+        assert!(built.get() <= 8);
+    }
+
+    #[test]
+    fn scroll_stops_at_the_end_of_the_list() {
+        let mut list = list(100, Rc::new(Cell::new(0)));
+        assert!(list.scroll_by(1_000_000., VIEWPORT));
+        // the last item ends at the bottom of the view
+        assert_eq!((list.first_item, list.first_item_offset), (96, 20.));
+        assert!(!list.scroll_by(10., VIEWPORT));
+        assert!(list.scroll_by(-1_000_000., VIEWPORT));
+        assert_eq!((list.first_item, list.first_item_offset), (0, 0.));
+    }
+}
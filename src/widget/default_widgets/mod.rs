@@ -1,11 +1,20 @@
 
 
 pub(crate) mod align;
+pub(crate) mod animated_background;
+pub(crate) mod aspect_ratio;
 pub(crate) mod background;
 pub(crate) mod button;
 pub(crate) mod center;
+pub(crate) mod constrained_box;
 pub(crate) mod empty;
 pub(crate) mod expanded;
+pub(crate) mod fractionally_sized_box;
+pub(crate) mod grid;
+pub(crate) mod list_view;
 pub(crate) mod padder;
 pub(crate) mod scaffold;
+pub(crate) mod scroll_view;
 pub(crate) mod sized_box;
+pub(crate) mod stack;
+pub(crate) mod wrap;
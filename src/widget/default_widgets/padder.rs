@@ -1,9 +1,12 @@
-use std::num::NonZeroU32;
-
 use crate::{
-    app::event::AppEvent, geometry::size_requirements::WidgetSizeRequirement, Canvas, Rect, Widget,
+    app::{app_context::AppContext, event::AppEvent},
+    geometry::{constraints::SizeConstraints, edge_insets::EdgeInsets, size_requirements::WidgetSizeRequirement},
+    widget::layout::{ChildRects, LayoutContext},
+    Canvas,
+    Rect,
+    Size,
+    Widget,
 };
-use crate::utils::nonable::Nonable;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct PaddType(u8);
@@ -49,128 +52,104 @@ impl From<PaddType> for u32 {
 }
 
 /// The Padder widget will draw its child with a padding around it.
-/// The padding can be specified for each side of the child. The padding value is the same for all sides.
-/// If you want different padding values for each side, you can nest multiple Padder widgets.
-struct Padding {
-    pub padd_type: PaddType,
-    pub padd_amount: u32,
-}
-
-impl Padding {
-    pub fn top(&self) -> u32 {
-        (u32::from(self.padd_type & PaddType::TOP) >> 0) * self.padd_amount
-    }
-
-    pub fn bottom(&self) -> u32 {
-        (u32::from(self.padd_type & PaddType::BOTTOM) >> 1) * self.padd_amount
-    }
-
-    pub fn left(&self) -> u32 {
-        (u32::from(self.padd_type & PaddType::LEFT) >> 2) * self.padd_amount
-    }
-
-    pub fn right(&self) -> u32 {
-        (u32::from(self.padd_type & PaddType::RIGHT) >> 3) * self.padd_amount
-    }
-
-    pub fn vertical(&self) -> u32 {
-        self.top() + self.bottom()
-    }
-
-    pub fn horizontal(&self) -> u32 {
-        self.left() + self.right()
-    }
-}
-
+/// The padding is given with edge insets, so each side can have a different amount.
+/// For the same amount on some of the sides, use `Padder::new` with the sides to pad.
 pub struct Padder<UserEvent, Child: Widget<UserEvent>> {
     _m: core::marker::PhantomData<UserEvent>,
     child: Child,
-    padding: Padding,
+    padding: EdgeInsets,
+    child_rect: ChildRects,
 }
 
-// todo : this whole section is terrible and needs rewrite
 impl<UserEvent, Child: Widget<UserEvent>> Padder<UserEvent, Child> {
-    pub fn new(padd_type: PaddType, padd_amount: u32, child: Child) -> Self {
+    /// Pad the given sides of the child with the same amount.
+    pub fn new(padd_type: PaddType, padd_amount: f32, child: Child) -> Self {
+        Self::insets(EdgeInsets::only(padd_type, padd_amount), child)
+    }
+
+    /// Pad the child with the given amount on each side.
+    pub fn insets(padding: EdgeInsets, child: Child) -> Self {
         Padder {
             _m: core::marker::PhantomData,
             child,
-            padding: Padding { padd_type, padd_amount },
+            padding,
+            child_rect: ChildRects::new(),
         }
     }
 
-    fn get_child_remaining_width(&self, available_space: NonZeroU32) -> u32 {
-        available_space.get().checked_sub(self.padding.horizontal()).unwrap_or(0)
+    pub fn padding(&self) -> EdgeInsets {
+        self.padding
     }
 
-    fn get_child_remaining_height(&self, available_space: NonZeroU32) -> u32 {
-        available_space.get().max(self.padding.vertical()) - self.padding.vertical()
+    /// Change the padding. The padder takes another size, so its ancestors need `AppContext::request_layout`.
+    pub fn set_padding(&mut self, padding: EdgeInsets) {
+        self.padding = padding;
+        self.child_rect.invalidate();
     }
 
-    fn get_left_pad(&self, available_space: NonZeroU32, child_space: NonZeroU32) -> u32 {
-        if self.padding.left() == 0 {
-            return 0;
-        }
-        else {
-            let padd_space = available_space.get() - child_space.get();
-            if self.padding.right() == 0 {
-                padd_space
-            }
-            else {
-                padd_space / 2
-            }
+    fn compute_child_rect(&self, from_rect: Rect) -> Rect {
+        if let Some(child_rect) = self.child_rect.get_child(from_rect, 0) {
+            return child_rect;
         }
+        self.padding.deflate(from_rect)
     }
+}
 
-    fn get_top_pad(&self, available_space: NonZeroU32, child_space: NonZeroU32) -> u32 {
-        if self.padding.top() == 0 {
-            return 0;
-        }
-        else {
-            let padd_space = available_space.get() - child_space.get();
-            if self.padding.bottom() == 0 {
-                padd_space
-            }
-            else {
-                padd_space / 2
-            }
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Padder<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+    fn draw(&self, buffer: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(buffer, self.compute_child_rect(rect), context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        self.padding.inflate_requirements(self.child.min_space_requirements())
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let (horizontal, vertical) = (self.padding.horizontal(), self.padding.vertical());
+        let child_size = self.child.layout(constraints.shrink(horizontal, vertical), context);
+        let size = constraints.constrain(Size::new(child_size.width + horizontal, child_size.height + vertical));
+        self.child_rect.invalidate();
+        let child_rect = self.compute_child_rect(Rect::from_size(size));
+        // the padder might be bigger than the child asked for, and it fills all the space inside the padding
+        if child_rect.size() != child_size {
+            self.child.layout(SizeConstraints::tight_for(child_rect), context);
         }
+        self.child_rect.store(size, vec![child_rect]);
+        size
     }
 
-    fn compute_child_rect(&self, from_rect: softbuffer::Rect) -> Option<softbuffer::Rect> {
-        let child_width = NonZeroU32::new(self.get_child_remaining_width(from_rect.width))?;
-        let child_height = NonZeroU32::new(self.get_child_remaining_height(from_rect.height))?;
-        let left_pad = self.get_left_pad(from_rect.width, child_width);
-        let top_pad = self.get_top_pad(from_rect.height, child_height);
-        Some(softbuffer::Rect {
-            x: from_rect.x + left_pad,
-            y: from_rect.y + top_pad,
-            width: child_width,
-            height: child_height,
-        })
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, self.compute_child_rect(rect), context)
     }
 }
 
-impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Padder<UserEvent, Child> {
-    type EventResponse = Child::EventResponse;
-    fn draw(&self, buffer: &mut Canvas, rect: Rect) {
-        match self.compute_child_rect(rect) {
-            Some(rect) => self.child.draw(buffer, rect),
-            None => {}, // no space left for child, don't draw at all
-        };
+#[cfg(test)]
+mod tests {
+    use super::{PaddType, Padder};
+    use crate::{Assets, EdgeInsets, Empty, LayoutContext, Rect, SizeConstraints, SizedBox, Widget, WidgetSizeRequirement};
+
+    #[test]
+    fn requirements_include_the_padding() {
+        let padder: Padder<(), _> = Padder::new(PaddType::VERTICAL, 5., SizedBox::new(40., 20., Empty::expand()));
+        assert_eq!(padder.min_space_requirements(), (WidgetSizeRequirement::Fixed { size: 40. }, WidgetSizeRequirement::Fixed { size: 30. }));
     }
 
-    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
-        let (child_width_requirement, child_height_requirement) = self.child.min_space_requirements();
-        (
-            child_width_requirement + self.padding.horizontal(),
-            child_height_requirement + self.padding.vertical(),
-        )
+    #[test]
+    fn loose_layout_wraps_the_child_with_the_padding() {
+        let mut padder: Padder<(), _> = Padder::insets(EdgeInsets::new(1., 2., 3., 4.), SizedBox::new(40., 20., Empty::expand()));
+        let mut assets = Assets::new();
+        let size = padder.layout(SizeConstraints::loose(200., 200.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (46., 24.));
+        assert_eq!(padder.compute_child_rect(Rect::from_size(size)), Rect::new(4., 1., 40., 20.));
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        match self.compute_child_rect(rect) {
-            Some(rect) => self.child.handle_event(event, rect),
-            None => Self::EventResponse::none(),
-        }
+    #[test]
+    fn padding_bigger_than_the_space_leaves_an_empty_child() {
+        let mut padder: Padder<(), _> = Padder::new(PaddType::ALL, 30., Empty::expand());
+        let mut assets = Assets::new();
+        let size = padder.layout(SizeConstraints::tight(50., 100.), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (50., 100.));
+        assert_eq!(padder.compute_child_rect(Rect::from_size(size)), Rect::new(30., 30., 0., 40.));
     }
-}
\ No newline at end of file
+}
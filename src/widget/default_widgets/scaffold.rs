@@ -1,11 +1,8 @@
-use std::num::NonZeroU32;
-
 use crate::{
-    app::event::AppEvent, geometry::{
-        screen_side::ScreenSide, size_requirements::WidgetSizeRequirement
-    }, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent}, geometry::{
+        constraints::SizeConstraints, screen_side::ScreenSide, size_requirements::WidgetSizeRequirement
+    }, widget::layout::{ChildRects, LayoutContext}, Rect, Size, Widget
 };
-use crate::utils::nonable::Nonable;
 
 /// The Scaffold is a widget with an app bar and a child.
 /// The app bar can be on the top, bottom, left or right of the child.
@@ -16,6 +13,8 @@ pub struct Scaffold<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEven
     appbar_side: ScreenSide,
     appbar: AppBar,
     child: Child,
+    /// Rects of the appbar and the child, computed during the layout pass.
+    child_rects: ChildRects,
 }
 
 impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<UserEvent, AppBar, Child> {
@@ -25,13 +24,14 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<Us
             appbar_side,
             appbar,
             child,
+            child_rects: ChildRects::new(),
         }
     }
 
     /// returns the width and offset of the appbar, and the width and offset of the child.
-    fn get_childs_width_and_offset(&self, available_space: NonZeroU32) -> ((u32, u32), (u32, u32)) {
+    fn get_childs_width_and_offset(&self, available_space: f32) -> ((f32, f32), (f32, f32)) {
         match self.appbar_side {
-            ScreenSide::Top | ScreenSide::Bottom => ((available_space.get(), 0), (available_space.get(), 0)),
+            ScreenSide::Top | ScreenSide::Bottom => ((available_space, 0.0), (available_space, 0.0)),
             ScreenSide::Left => {
                 let [appbar_width, child_width] = WidgetSizeRequirement::distribute_available_size(
                     [
@@ -40,7 +40,7 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<Us
                     ],
                     available_space
                 );
-                ((appbar_width, 0), (child_width, appbar_width))
+                ((appbar_width, 0.0), (child_width, appbar_width))
             },
             ScreenSide::Right => {
                 let [child_width, appbar_width] = WidgetSizeRequirement::distribute_available_size(
@@ -50,15 +50,15 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<Us
                     ],
                     available_space
                 );
-                ((appbar_width, child_width), (child_width, 0))
+                ((appbar_width, child_width), (child_width, 0.0))
             },
         }
     }
 
     /// returns the height and offset of the appbar, and the height and offset of the child.
-    fn get_childs_height_and_offset(&self, available_space: NonZeroU32) -> ((u32, u32), (u32, u32)) {
+    fn get_childs_height_and_offset(&self, available_space: f32) -> ((f32, f32), (f32, f32)) {
         match self.appbar_side {
-            ScreenSide::Left | ScreenSide::Right => ((available_space.get(), 0), (available_space.get(), 0)),
+            ScreenSide::Left | ScreenSide::Right => ((available_space, 0.0), (available_space, 0.0)),
             ScreenSide::Top => {
                 let [appbar_height, child_height] = WidgetSizeRequirement::distribute_available_size(
                     [
@@ -67,7 +67,7 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<Us
                     ],
                     available_space
                 );
-                ((appbar_height, 0), (child_height, appbar_height))
+                ((appbar_height, 0.0), (child_height, appbar_height))
             },
             ScreenSide::Bottom => {
                 let [child_height, appbar_height] = WidgetSizeRequirement::distribute_available_size(
@@ -77,44 +77,43 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Scaffold<Us
                     ],
                     available_space
                 );
-                ((appbar_height, child_height), (child_height, 0))
+                ((appbar_height, child_height), (child_height, 0.0))
             },
         }
     }
 
+    /// Compute the rects of the appbar and the child.
+    fn compute_child_rects(&self, rect: Rect) -> Vec<Rect> {
+        let ((appbar_width, appbar_x_offset), (child_width, child_x_offset)) = self.get_childs_width_and_offset(rect.width);
+        let ((appbar_height, appbar_y_offset), (child_height, child_y_offset)) = self.get_childs_height_and_offset(rect.height);
+        let appbar_rect = Rect {
+            x: rect.x + appbar_x_offset,
+            y: rect.y + appbar_y_offset,
+            width: appbar_width,
+            height: appbar_height,
+        };
+        let child_rect = Rect {
+            x: rect.x + child_x_offset,
+            y: rect.y + child_y_offset,
+            width: child_width,
+            height: child_height,
+        };
+        vec![appbar_rect, child_rect]
+    }
 
-
+    /// Rects of the appbar and the child, from the layout pass if it was done with this size.
+    fn get_child_rects(&self, rect: Rect) -> (Rect, Rect) {
+        let rects = self.child_rects.get(rect).unwrap_or_else(|| self.compute_child_rects(rect));
+        (rects[0], rects[1])
+    }
 }
 
 impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Widget<UserEvent> for Scaffold<UserEvent, AppBar, Child> {
     type EventResponse = (AppBar::EventResponse, Child::EventResponse);
-    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect) {
-        let ((appbar_width, appbar_x_offset), (child_width, child_x_offset)) = self.get_childs_width_and_offset(rect.width);
-        let ((appbar_height, appbar_y_offset), (child_height, child_y_offset)) = self.get_childs_height_and_offset(rect.height);
-        match (NonZeroU32::new(appbar_width), NonZeroU32::new(appbar_height)) {
-            (Some(width), Some(height)) => self.appbar.draw(canvas, softbuffer::Rect {
-                x: rect.x + appbar_x_offset,
-                y: rect.y + appbar_y_offset,
-                width,
-                height,
-            }),
-            _ => {
-                #[cfg(debug_assertions)]
-                println!("Unable to draw scaffold appbar: no space!")
-            },
-        };
-        match (NonZeroU32::new(child_width), NonZeroU32::new(child_height)) {
-            (Some(width), Some(height)) => self.child.draw(canvas, softbuffer::Rect {
-                x: rect.x + child_x_offset,
-                y: rect.y + child_y_offset,
-                width,
-                height,
-            }),
-            _ => {
-                #[cfg(debug_assertions)]
-                println!("Unable to draw scaffold child: no space!")
-            },
-        };
+    fn draw(&self, canvas: &mut crate::drawing::canvas::Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        let (appbar_rect, child_rect) = self.get_child_rects(rect);
+        self.appbar.draw(canvas, appbar_rect, context);
+        self.child.draw(canvas, child_rect, context);
     }
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
@@ -136,28 +135,20 @@ impl<UserEvent, AppBar: Widget<UserEvent>, Child: Widget<UserEvent>> Widget<User
         }
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let size = constraints.fit(self.min_space_requirements());
+        let rects = self.compute_child_rects(Rect::from_size(size));
+        self.appbar.layout(SizeConstraints::tight_for(rects[0]), context);
+        self.child.layout(SizeConstraints::tight_for(rects[1]), context);
+        self.child_rects.store(size, rects);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
         // we can't or the function calls, as lazy eval may skip a event propagation.
-        let ((appbar_width, appbar_x_offset), (child_width, child_x_offset)) = self.get_childs_width_and_offset(rect.width);
-        let ((appbar_height, appbar_y_offset), (child_height, child_y_offset)) = self.get_childs_height_and_offset(rect.height);
-        let app_bar_reponse = match (NonZeroU32::new(appbar_width), NonZeroU32::new(appbar_height)) {
-            (Some(width), Some(height)) => self.appbar.handle_event(event, softbuffer::Rect {
-                x: rect.x + appbar_x_offset,
-                y: rect.y + appbar_y_offset,
-                width,
-                height,
-            }),
-            _ => AppBar::EventResponse::none(), // either width or height is 0, so we don't draw the appbar
-        };
-        let child_response = match (NonZeroU32::new(child_width), NonZeroU32::new(child_height)) {
-            (Some(width), Some(height)) => self.child.handle_event(event, softbuffer::Rect {
-                x: rect.x + child_x_offset,
-                y: rect.y + child_y_offset,
-                width,
-                height,
-            }),
-            _ => Child::EventResponse::none(), // either width or height is 0, so we don't draw the child
-        };
+        let (appbar_rect, child_rect) = self.get_child_rects(rect);
+        let app_bar_reponse = self.appbar.handle_event(event, appbar_rect, context);
+        let child_response = self.child.handle_event(event, child_rect, context);
         (app_bar_reponse, child_response)
     }
 }
@@ -0,0 +1,477 @@
+use std::num::NonZeroU32;
+
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
+
+use crate::{
+    app::{
+        app_context::AppContext,
+        event::{mouse_position::MousePosition, AppEvent},
+    },
+    drawing::{canvas::Canvas, color::Color},
+    geometry::{constraints::{SizeConstraints, UNBOUNDED}, size_requirements::WidgetSizeRequirement},
+    utils::nonable::Nonable,
+    widget::layout::{ChildRects, LayoutContext},
+    Offset,
+    Rect,
+    Size,
+    Widget,
+};
+
+/// Smallest length of a scrollbar thumb, so it can still be grabbed with very long content.
+const MIN_THUMB_LENGTH: f32 = 20.;
+
+/// Axes on which a scroll view can scroll.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollAxis {
+    Vertical,
+    Horizontal,
+    Both,
+}
+
+impl ScrollAxis {
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, ScrollAxis::Horizontal | ScrollAxis::Both)
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        matches!(self, ScrollAxis::Vertical | ScrollAxis::Both)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollResponse {
+    None,
+    /// The content got scrolled, the new offset in pixels is given.
+    Scrolled {
+        x: f32,
+        y: f32,
+    },
+}
+
+impl Nonable for ScrollResponse {
+    fn none() -> Self { ScrollResponse::None }
+    fn is_none(&self) -> bool {
+        matches!(self, ScrollResponse::None)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
+/// Position of a scrollbar in the viewport.
+struct Scrollbar {
+    track: Rect,
+    thumb: Rect,
+    /// How many pixels of content are scrolled when the thumb moves by one pixel.
+    content_per_pixel: f32,
+}
+
+/// The scroll view gives its child as much space as it requires on the scrollable axes,
+/// and only shows the part of it that fits in the view.
+/// The content can be scrolled with the mouse wheel, the touchpad or by dragging the scrollbars.
+pub struct ScrollView<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    axis: ScrollAxis,
+    /// How far the content is scrolled, in pixels.
+    offset: Offset,
+    /// Offset when the current touchpad gesture started, to go back to it if the gesture is cancelled.
+    gesture_start: Option<Offset>,
+    mouse_position: MousePosition,
+    /// Scrollbar being dragged, with the distance between the mouse and the start of the thumb.
+    dragged_scrollbar: Option<(Orientation, f32)>,
+    /// Amount of pixels scrolled for one line of mouse wheel.
+    line_height: f32,
+    scrollbar_thickness: f32,
+    track_color: Color,
+    thumb_color: Color,
+    /// Rect of the content computed during the layout pass, before scrolling.
+    content_rect: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> ScrollView<UserEvent, Child> {
+    pub fn new(axis: ScrollAxis, child: Child) -> Self {
+        ScrollView {
+            _m: core::marker::PhantomData,
+            child,
+            axis,
+            offset: Offset::ZERO,
+            gesture_start: None,
+            mouse_position: MousePosition::OutOfWindow,
+            dragged_scrollbar: None,
+            line_height: 20.,
+            scrollbar_thickness: 8.,
+            track_color: Color::rgba(128, 128, 128, 50),
+            thumb_color: Color::rgba(128, 128, 128, 180),
+            content_rect: ChildRects::new(),
+        }
+    }
+
+    pub fn vertical(child: Child) -> Self {
+        Self::new(ScrollAxis::Vertical, child)
+    }
+
+    pub fn horizontal(child: Child) -> Self {
+        Self::new(ScrollAxis::Horizontal, child)
+    }
+
+    pub fn with_line_height(self, line_height: f32) -> Self {
+        ScrollView {
+            line_height,
+            ..self
+        }
+    }
+
+    pub fn with_scrollbar_thickness(self, scrollbar_thickness: f32) -> Self {
+        ScrollView {
+            scrollbar_thickness,
+            ..self
+        }
+    }
+
+    pub fn with_scrollbar_colors(self, track_color: Color, thumb_color: Color) -> Self {
+        ScrollView {
+            track_color,
+            thumb_color,
+            ..self
+        }
+    }
+
+    /// Current scroll offset, in pixels.
+    pub fn offset(&self) -> Offset {
+        self.offset
+    }
+
+    pub fn set_offset(&mut self, offset: Offset) {
+        self.offset = offset;
+    }
+
+    /// Size the child is laid out with.
+    fn content_size(&self, viewport: Rect) -> Size {
+        if let Some(content_rect) = self.content_rect.get_child(viewport, 0) {
+            return content_rect.size();
+        }
+        let (width_requirement, height_requirement) = self.child.min_space_requirements();
+        Size {
+            width: match self.axis.is_horizontal() {
+                true => width_requirement.unbounded_size(viewport.width),
+                false => viewport.width,
+            },
+            height: match self.axis.is_vertical() {
+                true => height_requirement.unbounded_size(viewport.height),
+                false => viewport.height,
+            },
+        }
+    }
+
+    /// Rect of the child once scrolled. It starts above and left of the view by the scroll offset.
+    fn content_rect(&self, viewport: Rect, content: Size, offset: Offset) -> Rect {
+        Rect::from_origin_size(viewport.origin() - offset, content)
+    }
+
+    /// Offset clamped so the content always covers the viewport.
+    /// The view or the content might have been resized since the offset was set, so this is computed on every use.
+    fn clamped_offset(&self, offset: Offset, viewport: Rect, content: Size) -> Offset {
+        let max_x = (content.width - viewport.width).max(0.);
+        let max_y = (content.height - viewport.height).max(0.);
+        Offset::new(offset.dx.clamp(0., max_x), offset.dy.clamp(0., max_y))
+    }
+
+    fn scrollbar(&self, orientation: Orientation, viewport: Rect, content: Size, offset: Offset) -> Option<Scrollbar> {
+        // when both scrollbars are shown, they leave the bottom right corner empty
+        let vertical_shown = self.axis.is_vertical() && content.height > viewport.height;
+        let horizontal_shown = self.axis.is_horizontal() && content.width > viewport.width;
+        let (shown, visible, total, offset, other_shown) = match orientation {
+            Orientation::Vertical => (vertical_shown, viewport.height, content.height, offset.dy, horizontal_shown),
+            Orientation::Horizontal => (horizontal_shown, viewport.width, content.width, offset.dx, vertical_shown),
+        };
+        if !shown {
+            return None;
+        }
+        let thickness = self.scrollbar_thickness.min(viewport.width).min(viewport.height);
+        let track_length = visible - if other_shown { thickness } else { 0. };
+        if thickness <= 0. || track_length <= 0. {
+            return None;
+        }
+        let thumb_length = (track_length * visible / total).max(MIN_THUMB_LENGTH).min(track_length);
+        let free_track = track_length - thumb_length;
+        let max_offset = total - visible;
+        let thumb_start = free_track * offset / max_offset;
+        let content_per_pixel = if free_track > 0. { max_offset / free_track } else { 0. };
+        Some(match orientation {
+            Orientation::Vertical => {
+                let x = viewport.right() - thickness;
+                Scrollbar {
+                    track: Rect::new(x, viewport.y, thickness, track_length),
+                    thumb: Rect::new(x, viewport.y + thumb_start, thickness, thumb_length),
+                    content_per_pixel,
+                }
+            },
+            Orientation::Horizontal => {
+                let y = viewport.bottom() - thickness;
+                Scrollbar {
+                    track: Rect::new(viewport.x, y, track_length, thickness),
+                    thumb: Rect::new(viewport.x + thumb_start, y, thumb_length, thickness),
+                    content_per_pixel,
+                }
+            },
+        })
+    }
+
+    /// Scroll by the given amount of pixels, and tell if the visible offset changed.
+    fn scroll_by(&mut self, delta: Offset, viewport: Rect, content: Size) -> ScrollResponse {
+        let previous = self.offset;
+        self.offset = self.clamped_offset(self.offset + delta, viewport, content);
+        self.scroll_response(previous)
+    }
+
+    fn scroll_response(&self, previous: Offset) -> ScrollResponse {
+        match self.offset {
+            offset if offset == previous => ScrollResponse::None,
+            offset => ScrollResponse::Scrolled { x: offset.dx, y: offset.dy },
+        }
+    }
+
+    /// Convert a wheel delta to pixels to scroll. Scrolling up moves the content down, so it reduces the offset.
+    fn wheel_delta(&self, delta: &MouseScrollDelta) -> Offset {
+        let (x, y) = match delta {
+            MouseScrollDelta::LineDelta(x, y) => (-x * self.line_height, -y * self.line_height),
+            MouseScrollDelta::PixelDelta(position) => (-position.x as f32, -position.y as f32),
+        };
+        match self.axis {
+            // most mice only have a vertical wheel, let it scroll horizontal views
+            ScrollAxis::Horizontal if x == 0. => Offset::new(y, 0.),
+            ScrollAxis::Horizontal => Offset::new(x, 0.),
+            ScrollAxis::Vertical => Offset::new(0., y),
+            ScrollAxis::Both => Offset::new(x, y),
+        }
+    }
+
+    /// Follow a wheel or touchpad gesture, and scroll with it unless the content already used it.
+    fn scroll_wheel(&mut self, delta: &MouseScrollDelta, phase: TouchPhase, scroll: bool, viewport: Rect, content: Size) -> ScrollResponse {
+        let delta = match scroll {
+            true => self.wheel_delta(delta),
+            false => Offset::ZERO,
+        };
+        match phase {
+            TouchPhase::Started => {
+                self.gesture_start = Some(self.offset);
+                self.scroll_by(delta, viewport, content)
+            },
+            TouchPhase::Moved => self.scroll_by(delta, viewport, content),
+            TouchPhase::Ended => {
+                self.gesture_start = None;
+                self.scroll_by(delta, viewport, content)
+            },
+            TouchPhase::Cancelled => {
+                let previous = self.offset;
+                if let Some(start) = self.gesture_start.take() {
+                    self.offset = self.clamped_offset(start, viewport, content);
+                }
+                self.scroll_response(previous)
+            },
+        }
+    }
+
+    /// Mouse position along the given orientation.
+    fn mouse_coordinate(&self, orientation: Orientation) -> Option<f32> {
+        match (self.mouse_position, orientation) {
+            (MousePosition::InWindow(point), Orientation::Vertical) => Some(point.y),
+            (MousePosition::InWindow(point), Orientation::Horizontal) => Some(point.x),
+            (MousePosition::OutOfWindow, _) => None,
+        }
+    }
+
+    /// Mouse position given to the content. Anything outside of the view is hidden, so the mouse can't be on the content there.
+    fn content_mouse_position(&self, viewport: Rect) -> MousePosition {
+        match self.mouse_position.is_in_rect(viewport) {
+            true => self.mouse_position,
+            false => MousePosition::OutOfWindow,
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for ScrollView<UserEvent, Child> {
+    type EventResponse = (ScrollResponse, Child::EventResponse);
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        let content = self.content_size(rect);
+        let offset = self.clamped_offset(self.offset, rect, content);
+        canvas.with_clip(rect, |canvas| {
+            self.child.draw(canvas, self.content_rect(rect, content, offset), context);
+        });
+        // scrollbars are drawn over the content
+        for orientation in [Orientation::Vertical, Orientation::Horizontal] {
+            if let Some(scrollbar) = self.scrollbar(orientation, rect, content, offset) {
+                canvas.fill_rect(scrollbar.track, self.track_color);
+                canvas.fill_rect(scrollbar.thumb, self.thumb_color);
+            }
+        }
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        // the view can be as small as needed on the scrollable axes
+        let (width, height) = self.child.min_space_requirements();
+        let flex = WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } };
+        (
+            if self.axis.is_horizontal() { flex } else { width },
+            if self.axis.is_vertical() { flex } else { height },
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the child can be as big as it wants on the scrollable axes, but at least as big as the view
+        let size = constraints.fit(self.min_space_requirements());
+        let content_constraints = SizeConstraints {
+            min_width: size.width,
+            max_width: if self.axis.is_horizontal() { UNBOUNDED } else { size.width },
+            min_height: size.height,
+            max_height: if self.axis.is_vertical() { UNBOUNDED } else { size.height },
+        };
+        let content_size = self.child.layout(content_constraints, context);
+        let content_size = Size::new(content_size.width.max(size.width), content_size.height.max(size.height));
+        self.content_rect.store(size, vec![Rect::from_size(content_size)]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let content = self.content_size(rect);
+        self.offset = self.clamped_offset(self.offset, rect, content);
+        let offset = self.offset;
+
+        let own_response = match event {
+            AppEvent::CursorMoved { position } => {
+                self.mouse_position = *position;
+                let dragged = self.dragged_scrollbar.and_then(|(orientation, grab)| {
+                    let scrollbar = self.scrollbar(orientation, rect, content, offset)?;
+                    let mouse = self.mouse_coordinate(orientation)?;
+                    let thumb_start = match orientation {
+                        Orientation::Vertical => scrollbar.thumb.y,
+                        Orientation::Horizontal => scrollbar.thumb.x,
+                    };
+                    // move the thumb so it stays at the same place under the mouse
+                    let thumb_delta = mouse - grab - thumb_start;
+                    let content_delta = thumb_delta * scrollbar.content_per_pixel;
+                    Some(match orientation {
+                        Orientation::Vertical => Offset::new(0., content_delta),
+                        Orientation::Horizontal => Offset::new(content_delta, 0.),
+                    })
+                });
+                match dragged {
+                    Some(delta) => self.scroll_by(delta, rect, content),
+                    None => ScrollResponse::None,
+                }
+            },
+            AppEvent::MouseWheel { delta, phase } => {
+                // the innermost view under the mouse scrolls first, and this one only takes the wheel once the content does not use it
+                let child_response = self.child.handle_event(event, self.content_rect(rect, content, offset), context);
+                let own_response = match self.mouse_position.is_in_rect(rect) || self.gesture_start.is_some() {
+                    true => self.scroll_wheel(delta, *phase, child_response.is_none(), rect, content),
+                    false => ScrollResponse::None,
+                };
+                return match own_response {
+                    // the content moved under the mouse, it is told where the mouse is now
+                    ScrollResponse::Scrolled { .. } => {
+                        let position = self.content_mouse_position(rect);
+                        let content_rect = self.content_rect(rect, content, self.offset);
+                        (own_response, self.child.handle_event(&AppEvent::CursorMoved { position }, content_rect, context))
+                    },
+                    ScrollResponse::None => (own_response, child_response),
+                };
+            },
+            AppEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left } => {
+                let pressed_scrollbar = [Orientation::Vertical, Orientation::Horizontal].into_iter().find_map(|orientation| {
+                    let scrollbar = self.scrollbar(orientation, rect, content, offset)?;
+                    match self.mouse_position.is_in_rect(scrollbar.track) {
+                        true => Some((orientation, scrollbar)),
+                        false => None,
+                    }
+                });
+                match pressed_scrollbar {
+                    Some((orientation, scrollbar)) => {
+                        let mouse = self.mouse_coordinate(orientation).unwrap_or(0.);
+                        let (thumb_start, thumb_length) = match orientation {
+                            Orientation::Vertical => (scrollbar.thumb.y, scrollbar.thumb.height),
+                            Orientation::Horizontal => (scrollbar.thumb.x, scrollbar.thumb.width),
+                        };
+                        let response = match self.mouse_position.is_in_rect(scrollbar.thumb) {
+                            true => ScrollResponse::None,
+                            false => {
+                                // pressing the track jumps there, with the thumb centered on the mouse
+                                let thumb_delta = mouse - thumb_start - thumb_length / 2.;
+                                let content_delta = thumb_delta * scrollbar.content_per_pixel;
+                                let delta = match orientation {
+                                    Orientation::Vertical => Offset::new(0., content_delta),
+                                    Orientation::Horizontal => Offset::new(content_delta, 0.),
+                                };
+                                self.scroll_by(delta, rect, content)
+                            },
+                        };
+                        let thumb_start = self.scrollbar(orientation, rect, content, self.offset)
+                            .map(|scrollbar| match orientation {
+                                Orientation::Vertical => scrollbar.thumb.y,
+                                Orientation::Horizontal => scrollbar.thumb.x,
+                            })
+                            .unwrap_or(thumb_start);
+                        self.dragged_scrollbar = Some((orientation, mouse - thumb_start));
+                        // the press is for the scrollbar, the content does not receive it
+                        return (response, Child::EventResponse::none());
+                    },
+                    None => ScrollResponse::None,
+                }
+            },
+            AppEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left } => {
+                self.dragged_scrollbar = None;
+                ScrollResponse::None
+            },
+            _ => ScrollResponse::None,
+        };
+
+        // the child is moved by the scroll offset, and the mouse is hidden from it outside of the view
+        let content_rect = self.content_rect(rect, content, self.offset);
+        let content_position = self.content_mouse_position(rect);
+        let child_response = match event {
+            // the content moved under the mouse, it is only told where the mouse is now
+            AppEvent::CursorMoved { .. } =>
+                self.child.handle_event(&AppEvent::CursorMoved { position: content_position }, content_rect, context),
+            _ => self.child.handle_event(event, content_rect, context),
+        };
+
+        (own_response, child_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::{MouseScrollDelta, TouchPhase};
+
+    use super::{ScrollResponse, ScrollView};
+    use crate::{AppContext, AppEvent, Empty, MousePosition, Point, Rect, SizedBox, Widget};
+
+    fn wheel_down() -> AppEvent<()> {
+        AppEvent::MouseWheel { delta: MouseScrollDelta::LineDelta(0., -1.), phase: TouchPhase::Moved }
+    }
+
+    #[test]
+    fn wheel_scrolls_the_inner_view_until_it_reaches_its_end() {
+        let inner = ScrollView::vertical(SizedBox::height(200., Empty::expand()));
+        let mut outer = ScrollView::vertical(SizedBox::height(150., inner));
+        let rect = Rect::new(0., 0., 100., 100.);
+        let mut context = AppContext::new();
+        outer.handle_event(&AppEvent::CursorMoved { position: MousePosition::InWindow(Point::new(50., 50.)) }, rect, &mut context);
+
+        let (outer_response, (inner_response, _)) = outer.handle_event(&wheel_down(), rect, &mut context);
+        assert_eq!(outer_response, ScrollResponse::None);
+        assert_eq!(inner_response, ScrollResponse::Scrolled { x: 0., y: 20. });
+
+        // at the end of its content, the inner view lets the outer one scroll
+        outer.handle_event(&wheel_down(), rect, &mut context);
+        outer.handle_event(&wheel_down(), rect, &mut context);
+        let (outer_response, (inner_response, _)) = outer.handle_event(&wheel_down(), rect, &mut context);
+        assert_eq!(outer_response, ScrollResponse::Scrolled { x: 0., y: 20. });
+        assert_eq!(inner_response, ScrollResponse::None);
+    }
+}
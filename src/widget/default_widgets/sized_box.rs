@@ -1,13 +1,17 @@
-use std::num::NonZeroU32;
-
 use crate::{
-    app::event::AppEvent, drawing::canvas::Canvas, geometry::size_requirements::WidgetSizeRequirement, Rect, Widget
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    widget::layout::LayoutContext,
+    Rect,
+    Size,
+    Widget
 };
 
 enum SizedBoxConstraints {
-    Width(NonZeroU32),
-    Height(NonZeroU32),
-    Both(NonZeroU32, NonZeroU32),
+    Width(f32),
+    Height(f32),
+    Both(f32, f32),
 }
 
 /// The Sized box provide fixed size constraints to it's child.
@@ -21,9 +25,9 @@ pub struct SizedBox<UserEvent, Child: Widget<UserEvent>> {
 }
 
 impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
-    pub fn new(width: u32, height: u32, child: Child) -> Self {
-        let width = NonZeroU32::new(width).expect("Width of a sized box must be non-zero");
-        let height = NonZeroU32::new(height).expect("Height of a sized box must be non-zero");
+    pub fn new(width: f32, height: f32, child: Child) -> Self {
+        assert!(width >= 0.0, "Width of a sized box must not be negative");
+        assert!(height >= 0.0, "Height of a sized box must not be negative");
         SizedBox {
             _m: core::marker::PhantomData,
             constraints: SizedBoxConstraints::Both(width, height),
@@ -31,8 +35,8 @@ impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
         }
     }
 
-    pub fn width(width: u32, child: Child) -> Self {
-        let width = NonZeroU32::new(width).expect("Width of a sized box must be non-zero");
+    pub fn width(width: f32, child: Child) -> Self {
+        assert!(width >= 0.0, "Width of a sized box must not be negative");
         SizedBox {
             _m: core::marker::PhantomData,
             constraints: SizedBoxConstraints::Width(width),
@@ -40,8 +44,8 @@ impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
         }
     }
 
-    pub fn height(height: u32, child: Child) -> Self {
-        let height = NonZeroU32::new(height).expect("Height of a sized box must be non-zero");
+    pub fn height(height: f32, child: Child) -> Self {
+        assert!(height >= 0.0, "Height of a sized box must not be negative");
         SizedBox {
             _m: core::marker::PhantomData,
             constraints: SizedBoxConstraints::Height(height),
@@ -49,7 +53,7 @@ impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
         }
     }
 
-    fn get_width(&self, available_space: NonZeroU32) -> NonZeroU32 {
+    fn get_width(&self, available_space: f32) -> f32 {
         #[cfg(debug_assertions)]
         // in debug mode, don't use the min that are far more optimized (no branching) but allow overflow debug
         match &self.constraints {
@@ -71,7 +75,7 @@ impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
         }
     }
 
-    fn get_height(&self, available_space: NonZeroU32) -> NonZeroU32 {
+    fn get_height(&self, available_space: f32) -> f32 {
         #[cfg(debug_assertions)]
         // in debug mode, don't use the min that are far more optimized (no branching) but allow overflow debug
         match &self.constraints {
@@ -96,14 +100,14 @@ impl<UserEvent, Child: Widget<UserEvent>> SizedBox<UserEvent, Child> {
 
 impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for SizedBox<UserEvent, Child> {
     type EventResponse = Child::EventResponse;
-    fn draw(&self, buffer: &mut Canvas, rect: Rect) {
-        let rect = softbuffer::Rect {
+    fn draw(&self, buffer: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        let rect = Rect {
             x: rect.x,
             y: rect.y,
             width: self.get_width(rect.width),
             height: self.get_height(rect.height),
         };
-        self.child.draw(buffer, rect);
+        self.child.draw(buffer, rect, context);
     }
 
     fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
@@ -123,7 +127,18 @@ impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for SizedBox<UserEve
         }
     }
 
-    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect) -> Self::EventResponse {
-        self.child.handle_event(event, rect)
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the constrained axes are fixed, the child chooses its size on the others
+        let size = constraints.fit(self.min_space_requirements());
+        let child_constraints = match self.constraints {
+            SizedBoxConstraints::Both(_, _) => SizeConstraints::tight(size.width, size.height),
+            SizedBoxConstraints::Width(_) => SizeConstraints { min_width: size.width, max_width: size.width, ..constraints },
+            SizedBoxConstraints::Height(_) => SizeConstraints { min_height: size.height, max_height: size.height, ..constraints },
+        };
+        constraints.constrain(self.child.layout(child_constraints, context))
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        self.child.handle_event(event, rect, context)
     }
 }
\ No newline at end of file
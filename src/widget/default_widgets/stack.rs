@@ -0,0 +1,155 @@
+use winit::event::ElementState;
+
+use crate::{
+    app::{
+        app_context::AppContext,
+        event::{mouse_position::MousePosition, AppEvent},
+    },
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    utils::nonable::Nonable,
+    widget::layout::LayoutContext,
+    Point,
+    Rect,
+    Size,
+    Widget,
+};
+
+pub(crate) mod positioned;
+
+/// The stack draws a child on top of another, both in the whole stack rect.
+/// To place the top child in a part of the stack, wrap it in a `Positioned`.
+/// More layers can be added with `on_top`, each new layer being drawn over the previous ones.
+///
+/// Events are delivered to the top child first. If propagation is stopped, overlays capture the mouse:
+/// mouse events over the top child are not delivered to the bottom child, which sees the mouse out of the window.
+/// Other events that the top child responded to are not delivered to the bottom child either.
+pub struct Stack<UserEvent, Bottom: Widget<UserEvent>, Top: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    bottom: Bottom,
+    top: Top,
+    stop_propagation: bool,
+    mouse_position: MousePosition,
+}
+
+impl<UserEvent, Bottom: Widget<UserEvent>, Top: Widget<UserEvent>> Stack<UserEvent, Bottom, Top> {
+    pub fn new(bottom: Bottom, top: Top) -> Self {
+        Stack {
+            _m: core::marker::PhantomData,
+            bottom,
+            top,
+            stop_propagation: false,
+            mouse_position: MousePosition::OutOfWindow,
+        }
+    }
+
+    /// Events handled by a child are not delivered to the children below it.
+    pub fn stop_propagation(self) -> Self {
+        Stack {
+            stop_propagation: true,
+            ..self
+        }
+    }
+
+    /// Add a child on top of all the others, with the same propagation behaviour.
+    pub fn on_top<NewTop: Widget<UserEvent>>(self, top: NewTop) -> Stack<UserEvent, Self, NewTop> {
+        let stop_propagation = self.stop_propagation;
+        Stack {
+            _m: core::marker::PhantomData,
+            bottom: self,
+            top,
+            stop_propagation,
+            mouse_position: MousePosition::OutOfWindow,
+        }
+    }
+}
+
+impl<UserEvent, Bottom: Widget<UserEvent>, Top: Widget<UserEvent>> Widget<UserEvent> for Stack<UserEvent, Bottom, Top> {
+    type EventResponse = (Bottom::EventResponse, Top::EventResponse);
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.bottom.draw(canvas, rect, context);
+        self.top.draw(canvas, rect, context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        // children are on top of each other, so the stack needs to fit both on each axis
+        let (bottom_width, bottom_height) = self.bottom.min_space_requirements();
+        let (top_width, top_height) = self.top.min_space_requirements();
+        (bottom_width | top_width, bottom_height | top_height)
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // both children are drawn in the whole stack rect, so they are laid out with its size
+        let size = constraints.fit(self.min_space_requirements());
+        let child_constraints = SizeConstraints::tight(size.width, size.height);
+        self.bottom.layout(child_constraints, context);
+        self.top.layout(child_constraints, context);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        if let AppEvent::CursorMoved { position } = event {
+            self.mouse_position = *position;
+        }
+        let covered = self.stop_propagation && match self.mouse_position {
+            MousePosition::InWindow(point) => self.top.hit_test(point, rect),
+            MousePosition::OutOfWindow => false,
+        };
+        let top_response = self.top.handle_event(event, rect, context);
+        let bottom_response = match event {
+            // the bottom child is hidden under the top one, so it does not get the mouse
+            AppEvent::CursorMoved { .. } if covered => {
+                self.bottom.handle_event(&AppEvent::CursorMoved { position: MousePosition::OutOfWindow }, rect, context)
+            },
+            // releases are always delivered, so a child pressed before being covered knows about it
+            AppEvent::MouseInput { state: ElementState::Pressed, .. } | AppEvent::MouseWheel { .. } if covered => Bottom::EventResponse::none(),
+            AppEvent::CursorMoved { .. } => self.bottom.handle_event(event, rect, context),
+            _ if self.stop_propagation && !top_response.is_none() => Bottom::EventResponse::none(),
+            _ => self.bottom.handle_event(event, rect, context),
+        };
+        (bottom_response, top_response)
+    }
+
+    fn hit_test(&self, point: Point, rect: Rect) -> bool {
+        self.top.hit_test(point, rect) || self.bottom.hit_test(point, rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit::event::{ElementState, MouseButton};
+
+    use super::Stack;
+    use crate::{AppContext, AppEvent, Button, ButtonResponse, ButtonState, Empty, MousePosition, Point, Positioned, Rect, Widget};
+
+    fn move_to(x: f32, y: f32) -> AppEvent<()> {
+        AppEvent::CursorMoved { position: MousePosition::InWindow(Point::new(x, y)) }
+    }
+
+    #[test]
+    fn top_child_hides_the_mouse_from_the_bottom_one() {
+        let overlay = Positioned::new(Button::new(Empty::expand())).left(0.).top(0.).width(50.).height(50.);
+        let mut stack = Stack::new(Button::new(Empty::expand()), overlay).stop_propagation();
+        let rect = Rect::new(0., 0., 100., 100.);
+        let mut context = AppContext::new();
+
+        // over the overlay, only the overlay is hovered
+        let ((bottom, _), (top, _)) = stack.handle_event(&move_to(10., 10.), rect, &mut context);
+        assert_eq!(bottom, ButtonResponse::None);
+        assert_eq!(top, ButtonResponse::Changed { prev: ButtonState::Idle, new: ButtonState::Hovered });
+        let press = AppEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left };
+        let ((bottom, _), _) = stack.handle_event(&press, rect, &mut context);
+        assert_eq!(bottom, ButtonResponse::None);
+
+        // next to the overlay, the bottom child gets the mouse
+        let release = AppEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left };
+        stack.handle_event(&release, rect, &mut context);
+        let ((bottom, _), _) = stack.handle_event(&move_to(80., 80.), rect, &mut context);
+        assert_eq!(bottom, ButtonResponse::Changed { prev: ButtonState::Idle, new: ButtonState::Hovered });
+
+        // going back over the overlay leaves the bottom child
+        let ((bottom, _), _) = stack.handle_event(&move_to(10., 10.), rect, &mut context);
+        assert_eq!(bottom, ButtonResponse::Changed { prev: ButtonState::Hovered, new: ButtonState::Idle });
+    }
+}
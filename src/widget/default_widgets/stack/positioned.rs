@@ -0,0 +1,195 @@
+use winit::event::ElementState;
+
+use crate::{
+    app::{
+        app_context::AppContext,
+        event::{mouse_position::MousePosition, AppEvent},
+    },
+    drawing::canvas::Canvas,
+    geometry::{constraints::SizeConstraints, size_requirements::WidgetSizeRequirement},
+    utils::nonable::Nonable,
+    widget::layout::{ChildRects, LayoutContext},
+    Point,
+    Rect,
+    Size,
+    Widget,
+};
+
+/// Placement of a child on one axis: offsets from both sides, and size.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct AxisPlacement {
+    start: Option<f32>,
+    end: Option<f32>,
+    size: Option<f32>,
+}
+
+impl AxisPlacement {
+    /// Get the offset and size of the child on this axis.
+    /// Without a size, the child takes the size it requires if it is fixed, or stretches between the offsets otherwise.
+    fn place(&self, available: f32, requirement: WidgetSizeRequirement) -> (f32, f32) {
+        let natural_size = match requirement {
+            WidgetSizeRequirement::Fixed { size } => Some(size),
+            WidgetSizeRequirement::Min { min, .. } => Some(min),
+            WidgetSizeRequirement::MinMax { min, .. } => Some(min),
+            _ => None,
+        };
+        let (start, size) = match (self.start, self.end, self.size) {
+            (Some(start), Some(end), None) => (start, (available - start - end).max(0.0)),
+            (Some(start), _, Some(size)) => (start, size),
+            (None, Some(end), Some(size)) => ((available - end - size).max(0.0), size),
+            (start, end, None) => {
+                let free = (available - start.unwrap_or(0.0) - end.unwrap_or(0.0)).max(0.0);
+                let size = natural_size.unwrap_or(free).min(free);
+                match (start, end) {
+                    (None, Some(end)) => ((available - end - size).max(0.0), size),
+                    _ => (start.unwrap_or(0.0), size),
+                }
+            },
+            (None, None, Some(size)) => (0.0, size),
+        };
+        let start = start.clamp(0.0, available);
+        (start, size.clamp(0.0, available - start))
+    }
+
+    /// Space required in the parent to place the child.
+    fn requirement(&self, child_requirement: WidgetSizeRequirement) -> WidgetSizeRequirement {
+        let offsets = self.start.unwrap_or(0.0) + self.end.unwrap_or(0.0);
+        match self.size {
+            Some(size) => WidgetSizeRequirement::Fixed { size: size + offsets },
+            None => child_requirement + offsets,
+        }
+    }
+}
+
+/// Places its child in a part of the available rect, with offsets from the sides and an optional size.
+/// This is meant to be used in a `Stack`, to overlay a widget at a given place.
+///
+/// Mouse presses and wheel events are only given to the child when the mouse is over it,
+/// so a stack can deliver them to the children below.
+pub struct Positioned<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    child: Child,
+    horizontal: AxisPlacement,
+    vertical: AxisPlacement,
+    mouse_position: MousePosition,
+    child_rect: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Positioned<UserEvent, Child> {
+    /// Without any offset or size, the child is placed at the top left with the size it requires, or fills the rect if it is flexible.
+    pub fn new(child: Child) -> Self {
+        Positioned {
+            _m: core::marker::PhantomData,
+            child,
+            horizontal: AxisPlacement::default(),
+            vertical: AxisPlacement::default(),
+            mouse_position: MousePosition::OutOfWindow,
+            child_rect: ChildRects::new(),
+        }
+    }
+
+    /// Child filling the whole rect, with the given offsets on each side.
+    pub fn fill(left: f32, top: f32, right: f32, bottom: f32, child: Child) -> Self {
+        Positioned::new(child).left(left).top(top).right(right).bottom(bottom)
+    }
+
+    pub fn left(self, left: f32) -> Self {
+        Positioned {
+            horizontal: AxisPlacement { start: Some(left), ..self.horizontal },
+            ..self
+        }
+    }
+
+    pub fn top(self, top: f32) -> Self {
+        Positioned {
+            vertical: AxisPlacement { start: Some(top), ..self.vertical },
+            ..self
+        }
+    }
+
+    pub fn right(self, right: f32) -> Self {
+        Positioned {
+            horizontal: AxisPlacement { end: Some(right), ..self.horizontal },
+            ..self
+        }
+    }
+
+    pub fn bottom(self, bottom: f32) -> Self {
+        Positioned {
+            vertical: AxisPlacement { end: Some(bottom), ..self.vertical },
+            ..self
+        }
+    }
+
+    pub fn width(self, width: f32) -> Self {
+        Positioned {
+            horizontal: AxisPlacement { size: Some(width), ..self.horizontal },
+            ..self
+        }
+    }
+
+    pub fn height(self, height: f32) -> Self {
+        Positioned {
+            vertical: AxisPlacement { size: Some(height), ..self.vertical },
+            ..self
+        }
+    }
+
+    /// Rect of the child in the given rect.
+    fn child_rect(&self, rect: Rect) -> Rect {
+        if let Some(child_rect) = self.child_rect.get_child(rect, 0) {
+            return child_rect;
+        }
+        let (width_requirement, height_requirement) = self.child.min_space_requirements();
+        let (x, width) = self.horizontal.place(rect.width, width_requirement);
+        let (y, height) = self.vertical.place(rect.height, height_requirement);
+        Rect {
+            x: rect.x + x,
+            y: rect.y + y,
+            width,
+            height,
+        }
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Positioned<UserEvent, Child> {
+    type EventResponse = Child::EventResponse;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        self.child.draw(canvas, self.child_rect(rect), context);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        let (width, height) = self.child.min_space_requirements();
+        (self.horizontal.requirement(width), self.vertical.requirement(height))
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        let size = constraints.fit(self.min_space_requirements());
+        self.child_rect.invalidate();
+        let child_rect = self.child_rect(Rect::from_size(size));
+        self.child.layout(SizeConstraints::tight_for(child_rect), context);
+        self.child_rect.store(size, vec![child_rect]);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        if let AppEvent::CursorMoved { position } = event {
+            self.mouse_position = *position;
+        }
+        let child_rect = self.child_rect(rect);
+        match event {
+            // releases are always delivered, so a child pressed and then left knows about it
+            AppEvent::MouseInput { state: ElementState::Pressed, .. } | AppEvent::MouseWheel { .. } if !self.mouse_position.is_in_rect(child_rect) => {
+                Child::EventResponse::none()
+            },
+            _ => self.child.handle_event(event, child_rect, context),
+        }
+    }
+
+    fn hit_test(&self, point: Point, rect: Rect) -> bool {
+        // only the placed child covers the stack
+        let child_rect = self.child_rect(rect);
+        child_rect.contains(point) && self.child.hit_test(point, child_rect)
+    }
+}
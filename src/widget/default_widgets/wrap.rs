@@ -0,0 +1,222 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    app::{app_context::AppContext, event::AppEvent},
+    drawing::canvas::Canvas,
+    geometry::{
+        alignment::{HorizontalAlignment, VerticalAlignment},
+        constraints::SizeConstraints,
+        size_requirements::WidgetSizeRequirement,
+    },
+    widget::layout::{ChildRects, LayoutContext},
+    Rect,
+    Size,
+    Widget,
+};
+
+/// The wrap widget places its children from left to right, and starts a new line (a run)
+/// when there is no more horizontal space. This lets rows of items reflow when the wrap is resized.
+///
+/// Children are measured with their size requirements: they take their fixed, min or max size.
+/// Children without a preferred width take a whole run, and children without a preferred height take the height of their run.
+///
+/// All children have the same type. To use different widgets in the same wrap,
+/// use `Box<dyn Widget<UserEvent, EventResponse = R>>` as the child type.
+pub struct Wrap<UserEvent, Child: Widget<UserEvent>> {
+    _m: core::marker::PhantomData<UserEvent>,
+    children: Vec<Child>,
+    /// Horizontal space between two children of a run.
+    spacing: f32,
+    /// Vertical space between two runs.
+    run_spacing: f32,
+    /// Alignment of the children of a run in the wrap width.
+    run_alignment: HorizontalAlignment,
+    /// Alignment of the children that are smaller than their run.
+    cross_alignment: VerticalAlignment,
+    child_rects: ChildRects,
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Wrap<UserEvent, Child> {
+    pub fn new(children: Vec<Child>) -> Self {
+        Wrap {
+            _m: core::marker::PhantomData,
+            children,
+            spacing: 0.0,
+            run_spacing: 0.0,
+            run_alignment: HorizontalAlignment::Left,
+            cross_alignment: VerticalAlignment::Top,
+            child_rects: ChildRects::new(),
+        }
+    }
+
+    pub fn spaced(self, spacing: f32, run_spacing: f32) -> Self {
+        Wrap {
+            spacing,
+            run_spacing,
+            ..self
+        }
+    }
+
+    pub fn with_run_alignment(self, run_alignment: HorizontalAlignment) -> Self {
+        Wrap {
+            run_alignment,
+            ..self
+        }
+    }
+
+    pub fn with_cross_alignment(self, cross_alignment: VerticalAlignment) -> Self {
+        Wrap {
+            cross_alignment,
+            ..self
+        }
+    }
+
+    /// Children of the wrap, to add, remove or reorder them.
+    /// The wrap computes its runs again on next use, but its ancestors keep their layout:
+    /// call `AppContext::request_layout` so the window is laid out again.
+    pub fn children_mut(&mut self) -> &mut Vec<Child> {
+        self.child_rects.invalidate();
+        &mut self.children
+    }
+
+    /// Rect of each child. Children below the wrap get an empty rect at its bottom.
+    fn child_rects(&self, rect: Rect) -> Vec<Rect> {
+        if let Some(rects) = self.child_rects.get(rect) {
+            return rects;
+        }
+        let available_width = rect.width;
+        let sizes: Vec<(f32, Option<f32>)> = self.children.iter().map(|child| {
+            let (width, height) = child.min_space_requirements();
+            let width = width.preferred_size().map(|width| width.min(available_width)).unwrap_or(available_width);
+            (width, height.preferred_size())
+        }).collect();
+
+        let mut rects = Vec::with_capacity(sizes.len());
+        let mut run_start = 0;
+        let mut y = rect.y;
+        while run_start < sizes.len() {
+            // fill the run with as many children as possible, there is always at least one
+            let mut run_width = sizes[run_start].0;
+            let mut run_end = run_start + 1;
+            while run_end < sizes.len() && run_width + self.spacing + sizes[run_end].0 <= available_width {
+                run_width += self.spacing + sizes[run_end].0;
+                run_end += 1;
+            }
+            let run_height = sizes[run_start..run_end].iter()
+                .filter_map(|(_, height)| *height)
+                .fold(0.0, f32::max);
+            // children below the wrap are not shown
+            let run_top = y.min(rect.bottom());
+            let visible_height = run_height.min(rect.bottom() - run_top);
+
+            let mut x = rect.x + self.run_alignment.get_left_space((available_width - run_width).max(0.0));
+            for (width, height) in sizes[run_start..run_end].iter() {
+                let height = height.map(|height| height.min(visible_height)).unwrap_or(visible_height);
+                let child_y = run_top + self.cross_alignment.get_top_space(visible_height - height);
+                rects.push(Rect { x, y: child_y, width: *width, height });
+                x += width + self.spacing;
+            }
+
+            y += run_height + self.run_spacing;
+            run_start = run_end;
+        }
+        rects
+    }
+}
+
+impl<UserEvent, Child: Widget<UserEvent>> Widget<UserEvent> for Wrap<UserEvent, Child> {
+    /// The responses of the children, in order.
+    type EventResponse = Vec<Child::EventResponse>;
+
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, context: &mut AppContext<UserEvent>) {
+        for (child, child_rect) in self.children.iter().zip(self.child_rects(rect)) {
+            child.draw(canvas, child_rect, context);
+        }
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        // the wrap needs to fit its widest child, and its height depends on the width it is given.
+        // we can only require to fit the tallest child here, the layout pass gives the height for a given width.
+        let (width, height) = self.children.iter()
+            .map(|child| child.min_space_requirements())
+            .map(|(width, height)| (width.preferred_size(), height.preferred_size()))
+            .fold((None, None), |(max_width, max_height): (Option<f32>, Option<f32>), (width, height)| {
+                (max_option(max_width, width), max_option(max_height, height))
+            });
+        let flex = NonZeroU32::MIN;
+        (
+            width.map(|min| WidgetSizeRequirement::Min { min, flex }).unwrap_or(WidgetSizeRequirement::Flex { flex }),
+            height.map(|min| WidgetSizeRequirement::Min { min, flex }).unwrap_or(WidgetSizeRequirement::Flex { flex }),
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // once the width is known, the runs give the height the wrap needs
+        let width = constraints.fit(self.min_space_requirements()).width;
+        self.child_rects.invalidate();
+        let content_height = self.child_rects(Rect::new(0.0, 0.0, width, f32::INFINITY))
+            .into_iter()
+            .map(|child_rect| child_rect.bottom())
+            .fold(0.0, f32::max);
+        let size = constraints.constrain(Size::new(width, content_height));
+        let rects = self.child_rects(Rect::from_size(size));
+        for (child, child_rect) in self.children.iter_mut().zip(rects.iter()) {
+            child.layout(SizeConstraints::tight_for(*child_rect), context);
+        }
+        self.child_rects.store(size, rects);
+        size
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        let child_rects = self.child_rects(rect);
+        self.children.iter_mut().zip(child_rects).map(|(child, child_rect)| child.handle_event(event, child_rect, context)).collect()
+    }
+}
+
+/// The biggest of two optional values, or the one that is set.
+fn max_option(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Wrap;
+    use crate::{Assets, Empty, HorizontalAlignment, LayoutContext, Rect, SizeConstraints, SizedBox, VerticalAlignment, Widget};
+
+    fn chips(sizes: &[(f32, f32)]) -> Vec<SizedBox<(), Empty>> {
+        sizes.iter().map(|(width, height)| SizedBox::new(*width, *height, Empty::expand())).collect()
+    }
+
+    #[test]
+    fn children_go_to_the_next_run_when_the_width_is_full() {
+        let wrap: Wrap<(), _> = Wrap::new(chips(&[(40., 20.), (40., 20.), (40., 20.)])).spaced(10., 5.);
+        assert_eq!(wrap.child_rects(Rect::new(0., 0., 100., 100.)), vec![
+            Rect::new(0., 0., 40., 20.),
+            Rect::new(50., 0., 40., 20.),
+            Rect::new(0., 25., 40., 20.),
+        ]);
+    }
+
+    #[test]
+    fn runs_and_children_are_aligned() {
+        let wrap: Wrap<(), _> = Wrap::new(chips(&[(40., 20.), (40., 10.)]))
+            .spaced(10., 0.)
+            .with_run_alignment(HorizontalAlignment::Center)
+            .with_cross_alignment(VerticalAlignment::Bottom);
+        assert_eq!(wrap.child_rects(Rect::new(0., 0., 100., 100.)), vec![
+            Rect::new(5., 0., 40., 20.),
+            Rect::new(55., 10., 40., 10.),
+        ]);
+    }
+
+    #[test]
+    fn layout_takes_the_height_of_the_runs() {
+        let mut wrap: Wrap<(), _> = Wrap::new(chips(&[(40., 20.), (40., 20.), (40., 20.)])).spaced(10., 5.);
+        let mut assets = Assets::new();
+        let size = wrap.layout(SizeConstraints::tight(100., 0.).with_unbounded_height(), &mut LayoutContext::new(&mut assets));
+        assert_eq!((size.width, size.height), (100., 45.));
+    }
+}
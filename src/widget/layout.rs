@@ -0,0 +1,90 @@
+use crate::{app::assets::Assets, Rect, Size};
+#[cfg(feature = "text")]
+use crate::{drawing::canvas::text::measure_spans, SpanStyle, TextSpan, TextStyle};
+
+/// Services available to widgets during the layout pass.
+/// It gives access to the assets, so widgets can measure their content (for example, the height of wrapped text).
+pub struct LayoutContext<'a> {
+    assets: &'a mut Assets,
+}
+
+impl<'a> LayoutContext<'a> {
+    pub fn new(assets: &'a mut Assets) -> LayoutContext<'a> {
+        LayoutContext {
+            assets,
+        }
+    }
+
+    pub fn assets(&mut self) -> &mut Assets {
+        self.assets
+    }
+
+    /// Size of the text when its lines are wrapped at the given max width.
+    #[cfg(feature = "text")]
+    pub fn measure_text(&mut self, text: &str, style: &TextStyle, max_width: f32) -> Size {
+        measure_spans(self.assets, std::iter::once((text, &SpanStyle::new())), style, Self::text_width(max_width))
+    }
+
+    /// Size of the spans when their lines are wrapped at the given max width.
+    #[cfg(feature = "text")]
+    pub fn measure_rich_text(&mut self, spans: &[TextSpan], style: &TextStyle, max_width: f32) -> Size {
+        measure_spans(self.assets, spans.iter().map(|span| (span.text.as_str(), &span.style)), style, Self::text_width(max_width))
+    }
+
+    #[cfg(feature = "text")]
+    fn text_width(max_width: f32) -> Option<f32> {
+        match max_width.is_finite() {
+            true => Some(max_width),
+            false => None,
+        }
+    }
+}
+
+/// Rects of the children of a widget, computed during the layout pass.
+/// They are stored relative to the widget origin, and are only valid while the widget is given a rect of the size it was laid out with.
+/// This lets containers reuse the layout in `draw` and `handle_event`, and recompute it only when they are given another size.
+#[derive(Debug, Clone, Default)]
+pub struct ChildRects {
+    size: Option<Size>,
+    rects: Vec<Rect>,
+}
+
+impl ChildRects {
+    pub fn new() -> ChildRects {
+        ChildRects::default()
+    }
+
+    /// Store the rects of the children for a widget of the given size. Rects are relative to the widget origin.
+    pub fn store(&mut self, size: Size, rects: Vec<Rect>) {
+        self.size = Some(size);
+        self.rects = rects;
+    }
+
+    /// Forget the stored rects, so they are recomputed on next use.
+    /// Widgets call this when a change of their state moves their children, for example when children are added.
+    /// Only this widget forgets its layout, `AppContext::request_layout` lays out the whole window again.
+    pub fn invalidate(&mut self) {
+        self.size = None;
+        self.rects.clear();
+    }
+
+    /// Rects of the children in the given widget rect, if the widget was laid out with this size.
+    pub fn get(&self, rect: Rect) -> Option<Vec<Rect>> {
+        match self.is_valid_for(rect) {
+            true => Some(self.rects.iter().map(|child_rect| child_rect.translate(rect.origin().to_offset())).collect()),
+            false => None,
+        }
+    }
+
+    /// Rect of a single child in the given widget rect, if the widget was laid out with this size.
+    pub fn get_child(&self, rect: Rect, index: usize) -> Option<Rect> {
+        match self.is_valid_for(rect) {
+            true => Some(self.rects.get(index)?.translate(rect.origin().to_offset())),
+            false => None,
+        }
+    }
+
+    fn is_valid_for(&self, rect: Rect) -> bool {
+        self.size == Some(rect.size())
+    }
+}
@@ -1,2 +1,4 @@
+pub(crate) mod rich_text;
+pub(crate) mod selectable_text;
 pub(crate) mod text;
 pub(crate) mod text_style;
\ No newline at end of file
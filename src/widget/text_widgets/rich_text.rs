@@ -0,0 +1,67 @@
+use std::num::NonZeroU32;
+
+use crate::{
+    Widget,
+    WidgetSizeRequirement,
+    Canvas,
+    TextStyle,
+    Rect,
+    Size,
+    SizeConstraints,
+    LayoutContext,
+    AppContext,
+};
+
+use self::text_span::TextSpan;
+
+pub(crate) mod text_span;
+
+/// Text made of multiple spans, each of them with its own style overrides.
+/// All spans are laid out together, so they flow on the same lines like a single text would.
+/// The given style is the base style of all spans, and also provides the layout parameters (overflow, alignment).
+pub struct RichText<UserEvent> {
+    _m: core::marker::PhantomData<UserEvent>,
+    spans: Vec<TextSpan>,
+    style: TextStyle,
+}
+
+impl<UserEvent> RichText<UserEvent> {
+    pub fn new(spans: Vec<TextSpan>, style: TextStyle) -> Self {
+        RichText {
+            _m: core::marker::PhantomData,
+            spans,
+            style,
+        }
+    }
+
+    /// Replace the spans. Call `AppContext::request_layout` if the text may take another size.
+    pub fn set_spans(&mut self, spans: Vec<TextSpan>) {
+        self.spans = spans;
+    }
+
+    /// Edit the spans in place. If they may take another size, ask for a layout with `AppContext::request_layout`.
+    pub fn spans_mut(&mut self) -> &mut Vec<TextSpan> {
+        &mut self.spans
+    }
+}
+
+impl<UserEvent> Widget<UserEvent> for RichText<UserEvent> {
+    type EventResponse = ();
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, _context: &mut AppContext<UserEvent>) {
+        canvas.draw_rich_text(&self.spans, rect, &self.style);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        (
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the text fills the width it is given, and takes the height of its lines wrapped at that width
+        constraints.fill_or_measure(|| context.measure_rich_text(&self.spans, &self.style, constraints.max_width))
+    }
+
+    fn handle_event(&mut self, _: &crate::app::event::AppEvent<UserEvent>, _: Rect, _context: &mut AppContext<UserEvent>) -> Self::EventResponse {}
+}
@@ -0,0 +1,127 @@
+use crate::{Color, FontStyle, FontWeight, TextDecoration, TextStyle};
+
+
+/// Style overrides of a span of rich text.
+/// Any field left to None uses the value of the rich text style.
+/// Decorations are drawn on top of the ones of the rich text style.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+    pub color: Option<Color>,
+    pub weight: Option<FontWeight>,
+    pub style: Option<FontStyle>,
+    pub size: Option<f32>,
+    pub decorations: Vec<TextDecoration>,
+    pub highlight: Option<Color>,
+}
+
+impl SpanStyle {
+    /// Style with no overrides at all.
+    pub const fn new() -> SpanStyle {
+        SpanStyle {
+            color: None,
+            weight: None,
+            style: None,
+            size: None,
+            decorations: Vec::new(),
+            highlight: None,
+        }
+    }
+
+    /// Apply the overrides on top of the attributes built from the base text style.
+    /// Sizes are multiplied by the scale factor.
+    pub(crate) fn apply<'a>(&self, attrs: cosmic_text::Attrs<'a>, base: &TextStyle, scale_factor: f32) -> cosmic_text::Attrs<'a> {
+        let mut attrs = attrs;
+        if let Some(color) = self.color {
+            attrs = attrs.color(color.into());
+        }
+        if let Some(weight) = self.weight {
+            attrs = attrs.weight(weight);
+        }
+        if let Some(style) = self.style {
+            attrs = attrs.style(style);
+        }
+        if let Some(size) = self.size {
+            attrs = attrs.metrics(cosmic_text::Metrics::new(size * scale_factor, (size + base.additional_interline) * scale_factor));
+        }
+        attrs
+    }
+}
+
+/// A piece of text in a rich text, with its own style overrides.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub style: SpanStyle,
+}
+
+impl TextSpan {
+    pub fn new(text: String) -> TextSpan {
+        TextSpan {
+            text,
+            style: SpanStyle::new(),
+        }
+    }
+
+    pub fn colored(self, color: Color) -> TextSpan {
+        TextSpan {
+            style: SpanStyle { color: Some(color), ..self.style },
+            ..self
+        }
+    }
+
+    pub fn weighted(self, weight: FontWeight) -> TextSpan {
+        TextSpan {
+            style: SpanStyle { weight: Some(weight), ..self.style },
+            ..self
+        }
+    }
+
+    pub fn styled(self, style: FontStyle) -> TextSpan {
+        TextSpan {
+            style: SpanStyle { style: Some(style), ..self.style },
+            ..self
+        }
+    }
+
+    pub fn italic(self) -> TextSpan {
+        self.styled(FontStyle::Italic)
+    }
+
+    pub fn sized(self, size: f32) -> TextSpan {
+        TextSpan {
+            style: SpanStyle { size: Some(size), ..self.style },
+            ..self
+        }
+    }
+
+    /// Add a decoration line to the span, for example `TextDecoration::spell_check` to mark a misspelled word.
+    pub fn decorated(self, decoration: TextDecoration) -> TextSpan {
+        let mut decorations = self.style.decorations;
+        decorations.push(decoration);
+        TextSpan {
+            style: SpanStyle { decorations, ..self.style },
+            ..self
+        }
+    }
+
+    pub fn underlined(self) -> TextSpan {
+        self.decorated(TextDecoration::underline())
+    }
+
+    pub fn struck_through(self) -> TextSpan {
+        self.decorated(TextDecoration::strikethrough())
+    }
+
+    pub fn highlighted(self, color: Color) -> TextSpan {
+        TextSpan {
+            style: SpanStyle { highlight: Some(color), ..self.style },
+            ..self
+        }
+    }
+}
+
+impl From<&str> for TextSpan {
+    fn from(text: &str) -> Self {
+        TextSpan::new(text.to_string())
+    }
+}
@@ -0,0 +1,251 @@
+use std::{cell::RefCell, num::NonZeroU32, time::{Duration, Instant}};
+
+use winit::{event::{ElementState, MouseButton}, window::CursorIcon};
+
+use crate::{
+    app::{
+        app_context::AppContext,
+        event::{mouse_position::MousePosition, AppEvent},
+    },
+    drawing::canvas::text::TextLayout,
+    utils::nonable::Nonable,
+    Canvas,
+    Color,
+    Rect,
+    Size,
+    TextStyle,
+    Widget,
+    WidgetSizeRequirement,
+    SizeConstraints,
+    LayoutContext,
+};
+
+/// Maximum delay between two presses for them to be a double click.
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectionResponse {
+    None,
+    /// The selection changed, the new selected text is given so it can be copied.
+    Selected(String),
+    /// The selection was removed.
+    Cleared,
+}
+
+impl Nonable for SelectionResponse {
+    fn none() -> Self { SelectionResponse::None }
+    fn is_none(&self) -> bool {
+        matches!(self, SelectionResponse::None)
+    }
+}
+
+/// Read only text, that can be selected with the mouse.
+/// Dragging selects characters, and a double click selects a word.
+pub struct SelectableText<UserEvent> {
+    _m: core::marker::PhantomData<UserEvent>,
+    text: String,
+    style: TextStyle,
+    selection_color: Color,
+    /// The selection, as the cursor where it started and the cursor where it ends.
+    selection: Option<(cosmic_text::Cursor, cosmic_text::Cursor)>,
+    dragging: bool,
+    mouse_position: MousePosition,
+    last_press: Option<Instant>,
+    /// The events do not have access to the fonts, so the layout of the last draw is kept for hit testing.
+    layout: RefCell<Option<TextLayout>>,
+}
+
+impl<UserEvent> SelectableText<UserEvent> {
+    pub fn new(text: String, style: TextStyle) -> Self {
+        SelectableText {
+            _m: core::marker::PhantomData,
+            text,
+            style,
+            selection_color: Color::rgba(80, 140, 230, 120),
+            selection: None,
+            dragging: false,
+            mouse_position: MousePosition::OutOfWindow,
+            last_press: None,
+            layout: RefCell::new(None),
+        }
+    }
+
+    pub fn with_selection_color(self, selection_color: Color) -> Self {
+        SelectableText {
+            selection_color,
+            ..self
+        }
+    }
+
+    /// Change the text and clear the selection. Call `AppContext::request_layout` so the new lines get the height they need.
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+        self.selection = None;
+        self.layout = RefCell::new(None);
+    }
+
+    /// The currently selected text, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        match self.ordered_selection() {
+            Some((start, end)) if start != end => Some(self.text_between(start, end)),
+            _ => None,
+        }
+    }
+
+    fn ordered_selection(&self) -> Option<(cosmic_text::Cursor, cosmic_text::Cursor)> {
+        self.selection.map(|(anchor, head)| (anchor.min(head), anchor.max(head)))
+    }
+
+    /// Text of the given line, without its line ending.
+    fn line(&self, line: usize) -> &str {
+        let line = self.text.split('\n').nth(line).unwrap_or("");
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    /// Get the byte index in the line closest to the given index, in case the cursor points in text that was changed by the layout (ellipsis).
+    fn clamp_index(line: &str, index: usize) -> usize {
+        let mut index = index.min(line.len());
+        while !line.is_char_boundary(index) {
+            index -= 1;
+        }
+        index
+    }
+
+    fn text_between(&self, start: cosmic_text::Cursor, end: cosmic_text::Cursor) -> String {
+        (start.line..=end.line).map(|line_index| {
+            let line = self.line(line_index);
+            let from = if line_index == start.line { Self::clamp_index(line, start.index) } else { 0 };
+            let to = if line_index == end.line { Self::clamp_index(line, end.index) } else { line.len() };
+            &line[from..to.max(from)]
+        }).collect::<Vec<_>>().join("\n")
+    }
+
+    /// Get the cursors around the word at the given cursor.
+    /// Words are made of alphanumeric characters, any other character is a word on its own except for whitespaces that are grouped.
+    fn word_at(&self, cursor: cosmic_text::Cursor) -> (cosmic_text::Cursor, cosmic_text::Cursor) {
+        let line = self.line(cursor.line);
+        let index = Self::clamp_index(line, cursor.index);
+        let class = |c: char| match c {
+            c if c.is_alphanumeric() || c == '_' => 0,
+            c if c.is_whitespace() => 1,
+            _ => 2,
+        };
+        // use the character after the cursor, or the last one at the end of the line
+        let (position, target) = match (line[index..].chars().next(), line[..index].chars().next_back()) {
+            (Some(next), _) => (index, next),
+            (None, Some(previous)) => (index - previous.len_utf8(), previous),
+            (None, None) => return (cursor, cursor),
+        };
+        let (start, end) = match class(target) {
+            2 => (position, position + target.len_utf8()),
+            target_class => {
+                let start = line[..position].char_indices().rev()
+                    .take_while(|(_, c)| class(*c) == target_class)
+                    .last().map(|(i, _)| i).unwrap_or(position);
+                let end = line[position..].char_indices()
+                    .find(|(_, c)| class(*c) != target_class)
+                    .map(|(i, _)| position + i).unwrap_or(line.len());
+                (start, end)
+            },
+        };
+        (cosmic_text::Cursor::new(cursor.line, start), cosmic_text::Cursor::new(cursor.line, end))
+    }
+
+    /// Find the cursor under the mouse, using the layout of the last draw.
+    fn hit(&self, rect: Rect) -> Option<cosmic_text::Cursor> {
+        let (x, y) = match self.mouse_position {
+            MousePosition::InWindow(point) => (point.x - rect.x, point.y - rect.y),
+            MousePosition::OutOfWindow => return None,
+        };
+        self.layout.borrow().as_ref()
+            .and_then(|layout| layout.hit(x, y))
+            // the affinity only matters for editing, drop it so cursors compare on their position only
+            .map(|cursor| cosmic_text::Cursor::new(cursor.line, cursor.index))
+    }
+
+    fn selection_response(&self) -> SelectionResponse {
+        match self.selected_text() {
+            Some(text) => SelectionResponse::Selected(text),
+            None => SelectionResponse::Cleared,
+        }
+    }
+}
+
+impl<UserEvent> Widget<UserEvent> for SelectableText<UserEvent> {
+    type EventResponse = SelectionResponse;
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, _context: &mut AppContext<UserEvent>) {
+        let selection = self.ordered_selection().map(|(start, end)| (start, end, self.selection_color));
+        let layout = canvas.draw_selectable_text(&self.text, rect, &self.style, selection);
+        *self.layout.borrow_mut() = Some(layout);
+    }
+
+    fn min_space_requirements(&self) -> (WidgetSizeRequirement, WidgetSizeRequirement) {
+        (
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+            WidgetSizeRequirement::Flex { flex: unsafe { NonZeroU32::new_unchecked(1) } },
+        )
+    }
+
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the text fills the width it is given, and takes the height of its lines wrapped at that width
+        constraints.fill_or_measure(|| context.measure_text(&self.text, &self.style, constraints.max_width))
+    }
+
+    fn handle_event(&mut self, event: &AppEvent<UserEvent>, rect: Rect, context: &mut AppContext<UserEvent>) -> Self::EventResponse {
+        match event {
+            AppEvent::CursorMoved { position } => {
+                // show a text cursor while the mouse is over the text
+                let was_hovered = self.mouse_position.is_in_rect(rect);
+                self.mouse_position = *position;
+                match (was_hovered, self.mouse_position.is_in_rect(rect)) {
+                    (false, true) => context.set_cursor_icon(CursorIcon::Text),
+                    (true, false) => context.set_cursor_icon(CursorIcon::Default),
+                    _ => {},
+                }
+                match (self.dragging, self.selection, self.hit(rect)) {
+                    (true, Some((anchor, head)), Some(cursor)) if cursor != head => {
+                        self.selection = Some((anchor, cursor));
+                        self.selection_response()
+                    },
+                    _ => SelectionResponse::None,
+                }
+            },
+            AppEvent::MouseInput { state: ElementState::Pressed, button: MouseButton::Left } => {
+                let had_selection = self.selected_text().is_some();
+                let cursor = match self.mouse_position.is_in_rect(rect) {
+                    true => self.hit(rect),
+                    false => None,
+                };
+                let now = Instant::now();
+                let double_click = self.last_press.map(|last| now.duration_since(last) < DOUBLE_CLICK_DELAY).unwrap_or(false);
+                match cursor {
+                    Some(cursor) if double_click => {
+                        // a third click starts a new selection
+                        self.last_press = None;
+                        self.selection = Some(self.word_at(cursor));
+                        self.dragging = false;
+                    },
+                    Some(cursor) => {
+                        self.last_press = Some(now);
+                        self.selection = Some((cursor, cursor));
+                        self.dragging = true;
+                    },
+                    None => {
+                        self.last_press = None;
+                        self.selection = None;
+                    },
+                }
+                match (had_selection, self.selected_text()) {
+                    (_, Some(text)) => SelectionResponse::Selected(text),
+                    (true, None) => SelectionResponse::Cleared,
+                    (false, None) => SelectionResponse::None,
+                }
+            },
+            AppEvent::MouseInput { state: ElementState::Released, button: MouseButton::Left } => {
+                self.dragging = false;
+                SelectionResponse::None
+            },
+            _ => SelectionResponse::None,
+        }
+    }
+}
@@ -5,7 +5,11 @@ use crate::{
     WidgetSizeRequirement,
     Canvas,
     TextStyle,
-    Rect
+    Rect,
+    Size,
+    SizeConstraints,
+    LayoutContext,
+    AppContext,
 };
 
 pub(crate) mod text_overflow;
@@ -27,6 +31,7 @@ impl<UserEvent> Text<UserEvent> {
         }
     }
 
+    /// Change the text. Its lines may need another height, so call `AppContext::request_layout` too.
     pub fn set_text(&mut self, text: String) {
         self.text = text;
     }
@@ -34,7 +39,7 @@ impl<UserEvent> Text<UserEvent> {
 
 impl<UserEvent> Widget<UserEvent> for Text<UserEvent> {
     type EventResponse = ();
-    fn draw(&self, canvas: &mut Canvas, rect: Rect) {
+    fn draw(&self, canvas: &mut Canvas, rect: Rect, _context: &mut AppContext<UserEvent>) {
         canvas.draw_text(&self.text, rect, &self.style);
     }
 
@@ -45,7 +50,34 @@ impl<UserEvent> Widget<UserEvent> for Text<UserEvent> {
         )
     }
 
-    fn handle_event(&mut self, _: &crate::app::event::AppEvent<UserEvent>, _: Rect) -> Self::EventResponse {
+    fn layout(&mut self, constraints: SizeConstraints, context: &mut LayoutContext) -> Size {
+        // the text fills the width it is given, and takes the height of its lines wrapped at that width
+        constraints.fill_or_measure(|| context.measure_text(&self.text, &self.style, constraints.max_width))
+    }
+
+    fn handle_event(&mut self, _: &crate::app::event::AppEvent<UserEvent>, _: Rect, _context: &mut AppContext<UserEvent>) -> Self::EventResponse {
         ()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{text_overflow::TextOverflow, Text};
+    use crate::{Assets, LayoutContext, SizeConstraints, TextStyle, Widget};
+
+    #[test]
+    fn wrapped_text_takes_the_height_of_its_lines() {
+        let style = TextStyle::default().sized(20.).with_overflow(TextOverflow::NewLine);
+        let mut text: Text<()> = Text::new("The layout pass gives each widget constraints. ".repeat(3), style);
+        let mut assets = Assets::new();
+        let mut context = LayoutContext::new(&mut assets);
+        let wide = text.layout(SizeConstraints::loose(2000., 1000.), &mut context);
+        let narrow = text.layout(SizeConstraints::loose(100., 1000.), &mut context);
+        assert_eq!(narrow.width, 100.);
+        assert!(narrow.height > wide.height);
+        assert!(narrow.height < 1000.);
+        // a tight height is kept whatever the lines need
+        let tight = text.layout(SizeConstraints::tight(100., 30.), &mut context);
+        assert_eq!((tight.width, tight.height), (100., 30.));
+    }
+}
@@ -18,14 +18,27 @@ pub enum TextOverflow {
     NewLine,
 }
 
+impl TextOverflow {
+    /// Whether the overflow is replaced with an ellipsis.
+    pub fn is_ellipsis(&self) -> bool {
+        matches!(self, TextOverflow::CharEllipsis | TextOverflow::WordEllipsis)
+    }
+
+    /// Whether the overflow is handled at a word level, rather than a char or pixel level.
+    pub fn is_word_level(&self) -> bool {
+        matches!(self, TextOverflow::ClipWord | TextOverflow::WordEllipsis | TextOverflow::NewLine)
+    }
+}
+
 impl Into<cosmic_text::Wrap> for TextOverflow {
     fn into(self) -> cosmic_text::Wrap {
         match self {
             TextOverflow::ClipPixels => cosmic_text::Wrap::None,
             TextOverflow::ClipChar => cosmic_text::Wrap::Glyph,
             TextOverflow::ClipWord => cosmic_text::Wrap::Word,
-            TextOverflow::CharEllipsis => cosmic_text::Wrap::Glyph,
-            TextOverflow::WordEllipsis => cosmic_text::Wrap::Word,
+            // ellipsis overflows truncate the lines themselves, so the buffer must not wrap them.
+            TextOverflow::CharEllipsis => cosmic_text::Wrap::None,
+            TextOverflow::WordEllipsis => cosmic_text::Wrap::None,
             TextOverflow::NewLine => cosmic_text::Wrap::Word,
         }
     }
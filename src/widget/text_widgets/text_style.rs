@@ -1,10 +1,11 @@
-use crate::Color;
+use crate::{Color, VerticalAlignment};
 
-use self::font_family::FontFamily;
+use self::{font_family::FontFamily, text_decoration::TextDecoration};
 
 use super::text::{text_overflow::TextOverflow, TextAlign};
 
 pub(crate) mod font_family;
+pub(crate) mod text_decoration;
 
 pub type FontWeight = cosmic_text::Weight;
 pub type FontCharSpacing = cosmic_text::Stretch;
@@ -21,6 +22,9 @@ pub struct TextStyle {
     pub color: Color,
     pub overflow: TextOverflow,
     pub align: TextAlign,
+    pub vertical_align: VerticalAlignment,
+    /// Lines drawn along the whole text.
+    pub decorations: Vec<TextDecoration>,
 }
 
 impl TextStyle {
@@ -86,6 +90,23 @@ impl TextStyle {
             ..self
         }
     }
+
+    pub fn vertically_aligned(self, vertical_align: VerticalAlignment) -> TextStyle {
+        TextStyle {
+            vertical_align,
+            ..self
+        }
+    }
+
+    /// Add a decoration line to the text. Multiple decorations can be combined.
+    pub fn decorated(self, decoration: TextDecoration) -> TextStyle {
+        let mut decorations = self.decorations;
+        decorations.push(decoration);
+        TextStyle {
+            decorations,
+            ..self
+        }
+    }
 }
 
 impl<'a> Into<cosmic_text::Attrs<'a>> for &'a TextStyle {
@@ -110,6 +131,8 @@ impl Default for TextStyle {
             color: Color::BLACK,
             overflow: TextOverflow::ClipPixels,
             align: TextAlign::Left,
+            vertical_align: VerticalAlignment::Top,
+            decorations: Vec::new(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,88 @@
+use crate::Color;
+
+
+/// Where a decoration line is drawn, relative to the text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationLine {
+    /// Below the baseline.
+    Underline,
+    /// Through the middle of the lowercase letters.
+    Strikethrough,
+    /// Above the tallest letters.
+    Overline,
+}
+
+/// How a decoration line is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecorationStyle {
+    Solid,
+    /// A wave, as used to highlight spelling mistakes.
+    Wavy,
+    Dotted,
+}
+
+/// A line drawn along the text, such as an underline.
+/// The position and default thickness of the line are taken from the font metrics.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextDecoration {
+    pub line: DecorationLine,
+    pub style: DecorationStyle,
+    /// Color of the line. If None, the color of the decorated text is used.
+    pub color: Option<Color>,
+    /// Thickness of the line, in pixels. If None, the thickness recommended by the font is used.
+    pub thickness: Option<f32>,
+}
+
+impl TextDecoration {
+    pub const fn new(line: DecorationLine) -> TextDecoration {
+        TextDecoration {
+            line,
+            style: DecorationStyle::Solid,
+            color: None,
+            thickness: None,
+        }
+    }
+
+    pub const fn underline() -> TextDecoration {
+        TextDecoration::new(DecorationLine::Underline)
+    }
+
+    pub const fn strikethrough() -> TextDecoration {
+        TextDecoration::new(DecorationLine::Strikethrough)
+    }
+
+    pub const fn overline() -> TextDecoration {
+        TextDecoration::new(DecorationLine::Overline)
+    }
+
+    /// Wavy underline of the given color, the usual way to mark spelling mistakes.
+    pub const fn spell_check(color: Color) -> TextDecoration {
+        TextDecoration {
+            line: DecorationLine::Underline,
+            style: DecorationStyle::Wavy,
+            color: Some(color),
+            thickness: None,
+        }
+    }
+
+    pub fn styled(self, style: DecorationStyle) -> TextDecoration {
+        TextDecoration {
+            style,
+            ..self
+        }
+    }
+
+    pub fn colored(self, color: Color) -> TextDecoration {
+        TextDecoration {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    pub fn thick(self, thickness: f32) -> TextDecoration {
+        TextDecoration {
+            thickness: Some(thickness),
+            ..self
+        }
+    }
+}